@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context, Result};
+use axum::http::HeaderMap;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::prover::sha256_hex;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const ACCOUNT_HEADER: &str = "x-stellar-account";
+pub const SESSION_HEADER: &str = "x-session-id";
+pub const TIMESTAMP_HEADER: &str = "x-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-signature";
+
+// SEP-53 prefix — what Freighter's signMessage hashes in front of the message
+const SIGNED_MESSAGE_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+// Signed requests older (or newer) than this are rejected
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Most clients the rate limiter tracks at once. Past this, requests from
+/// clients it hasn't seen are refused until old windows finish.
+pub const DEFAULT_MAX_CLIENTS: usize = 4096;
+
+/// Who may call the proving endpoints. With no API keys and no accounts
+/// allowed to sign, the prover stays open, as before.
+pub struct Auth {
+    api_keys:         HashSet<String>,
+    allowed_accounts: HashSet<String>, // G... accounts whose signed requests are accepted
+}
+
+/// An admitted caller — also the key rate limits are counted against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Client {
+    Anonymous(IpAddr),
+    ApiKey(String), // short fingerprint, never the key itself
+    Stellar { account: String, session_id: u32 },
+}
+
+impl fmt::Display for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Client::Anonymous(ip) => write!(f, "ip:{ip}"),
+            Client::ApiKey(fingerprint) => write!(f, "key:{fingerprint}"),
+            Client::Stellar { account, session_id } => write!(f, "{account}#{session_id}"),
+        }
+    }
+}
+
+impl Client {
+    /// Limits apply per key or per Stellar account — a player can't dodge
+    /// them by opening a new session.
+    pub fn rate_key(&self) -> String {
+        match self {
+            Client::Stellar { account, .. } => account.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+impl Auth {
+    /// Signed requests are accepted only when `allowed_accounts` is non-empty,
+    /// and only from those accounts.
+    pub fn new(api_keys: HashSet<String>, allowed_accounts: HashSet<String>) -> Self {
+        Auth { api_keys, allowed_accounts }
+    }
+
+    /// One key per line; blank lines and `#` comments are skipped.
+    pub fn load_api_keys(path: &Path) -> Result<HashSet<String>> {
+        let text = fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        Ok(text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect())
+    }
+
+    /// Same format as `load_api_keys`, one G... account per line.
+    pub fn load_accounts(path: &Path) -> Result<HashSet<String>> {
+        let accounts = Self::load_api_keys(path)?;
+        if let Some(bad) = accounts.iter().find(|a| stellar_strkey::ed25519::PublicKey::from_string(a).is_err()) {
+            bail!("{}: {bad} is not a G... account", path.display());
+        }
+        Ok(accounts)
+    }
+
+    pub fn is_required(&self) -> bool {
+        !self.api_keys.is_empty() || self.signed_requests()
+    }
+
+    pub fn signed_requests(&self) -> bool {
+        !self.allowed_accounts.is_empty()
+    }
+
+    /// Identify the caller from its headers. `body` is the raw request body,
+    /// which a signed request's signature covers.
+    pub fn authenticate(&self, headers: &HeaderMap, body: &[u8], peer: Option<IpAddr>) -> Result<Client, String> {
+        if !self.is_required() {
+            return Ok(Client::Anonymous(peer.unwrap_or(IpAddr::from([0, 0, 0, 0]))));
+        }
+
+        if let Some(key) = header(headers, API_KEY_HEADER) {
+            if self.api_keys.contains(key) {
+                return Ok(Client::ApiKey(sha256_hex(key.as_bytes())[..12].to_string()));
+            }
+            return Err("Unknown API key".into());
+        }
+
+        if self.signed_requests() && headers.contains_key(SIGNATURE_HEADER) {
+            let client = verify_signed_request(headers, body)?;
+            return match &client {
+                Client::Stellar { account, .. } if self.allowed_accounts.contains(account) => Ok(client),
+                _ => Err("Account is not allowed to use this prover".into()),
+            };
+        }
+
+        Err(if self.signed_requests() {
+            format!("Expected {API_KEY_HEADER} or a signed request ({ACCOUNT_HEADER}, {SESSION_HEADER}, {TIMESTAMP_HEADER}, {SIGNATURE_HEADER})")
+        } else {
+            format!("Expected {API_KEY_HEADER}")
+        })
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn required<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, String> {
+    header(headers, name).ok_or_else(|| format!("Missing {name}"))
+}
+
+/// The string a client signs: `lantern-prover:{session_id}:{timestamp}:{sha256(body) hex}`.
+pub fn signed_message(session_id: u32, timestamp: u64, body: &[u8]) -> String {
+    format!("lantern-prover:{session_id}:{timestamp}:{}", sha256_hex(body))
+}
+
+// Signature is ed25519 over SHA256(SEP-53 prefix ‖ message), base64 encoded —
+// exactly what Freighter's signMessage returns for the player's G... account.
+fn verify_signed_request(headers: &HeaderMap, body: &[u8]) -> Result<Client, String> {
+    let account = required(headers, ACCOUNT_HEADER)?;
+    let session_id: u32 = required(headers, SESSION_HEADER)?
+        .parse()
+        .map_err(|_| format!("Invalid {SESSION_HEADER}"))?;
+    let timestamp: u64 = required(headers, TIMESTAMP_HEADER)?
+        .parse()
+        .map_err(|_| format!("Invalid {TIMESTAMP_HEADER}"))?;
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| e.to_string())?.as_secs();
+    if now.abs_diff(timestamp) > MAX_CLOCK_SKEW.as_secs() {
+        return Err("Signed request expired — check the client clock".into());
+    }
+
+    let public_key = stellar_strkey::ed25519::PublicKey::from_string(account)
+        .map_err(|_| format!("Invalid {ACCOUNT_HEADER}: expected a G... address"))?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key.0).map_err(|_| "Invalid Stellar public key".to_string())?;
+
+    let signature = BASE64_STANDARD
+        .decode(required(headers, SIGNATURE_HEADER)?)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or_else(|| format!("Invalid {SIGNATURE_HEADER}"))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(SIGNED_MESSAGE_PREFIX);
+    hasher.update(signed_message(session_id, timestamp, body).as_bytes());
+    verifying_key
+        .verify_strict(&hasher.finalize(), &signature)
+        .map_err(|_| "Signature does not match account".to_string())?;
+
+    Ok(Client::Stellar { account: account.to_string(), session_id })
+}
+
+/// Fixed-window request counter per client key, under a global cap shared
+/// by every client. The per-client map holds at most `max_clients` entries.
+#[derive(Clone)]
+pub struct RateLimiter {
+    per_minute:        u32, // 0 = unlimited
+    global_per_minute: u32, // 0 = unlimited
+    max_clients:       usize,
+    windows:           Arc<Mutex<Windows>>,
+}
+
+#[derive(Default)]
+struct Windows {
+    clients: HashMap<String, (Instant, u32)>,
+    global:  Option<(Instant, u32)>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        RateLimiter { per_minute, global_per_minute: 0, max_clients: DEFAULT_MAX_CLIENTS, windows: Arc::default() }
+    }
+
+    /// Also cap requests per minute across all clients together.
+    pub fn with_global_limit(mut self, per_minute: u32) -> Self {
+        self.global_per_minute = per_minute;
+        self
+    }
+
+    pub fn with_max_clients(mut self, max_clients: usize) -> Self {
+        self.max_clients = max_clients.max(1);
+        self
+    }
+
+    pub fn per_minute(&self) -> u32 {
+        self.per_minute
+    }
+
+    pub fn global_per_minute(&self) -> u32 {
+        self.global_per_minute
+    }
+
+    /// Count one request for `key`; on refusal, how long until a window resets.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        if self.per_minute == 0 && self.global_per_minute == 0 {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let Windows { clients, global } = &mut *windows;
+
+        let global = global.get_or_insert((now, 0));
+        if self.global_per_minute > 0 {
+            roll_window(global, now);
+            if global.1 >= self.global_per_minute {
+                return Err(RATE_WINDOW - now.duration_since(global.0));
+            }
+        }
+
+        if self.per_minute > 0 {
+            // Forget finished windows only once the map is full, and refuse
+            // new clients while it stays full, so fresh keys can't grow it
+            if !clients.contains_key(key) && clients.len() >= self.max_clients {
+                clients.retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+                if clients.len() >= self.max_clients {
+                    let oldest = clients.values().map(|(start, _)| *start).min().unwrap_or(now);
+                    return Err(RATE_WINDOW.saturating_sub(now.duration_since(oldest)));
+                }
+            }
+            let window = clients.entry(key.to_string()).or_insert((now, 0));
+            roll_window(window, now);
+            if window.1 >= self.per_minute {
+                return Err(RATE_WINDOW - now.duration_since(window.0));
+            }
+            window.1 += 1;
+        }
+        global.1 += 1;
+        Ok(())
+    }
+}
+
+fn roll_window((start, count): &mut (Instant, u32), now: Instant) {
+    if now.duration_since(*start) >= RATE_WINDOW {
+        *start = now;
+        *count = 0;
+    }
+}
+
+/// Caps how many proving requests are in flight at once. Requests beyond
+/// the cap are turned away instead of queueing without bound.
+#[derive(Clone)]
+pub struct ProveQueue {
+    slots:     Arc<Semaphore>,
+    max_depth: usize,
+}
+
+impl ProveQueue {
+    pub fn new(max_depth: usize) -> Self {
+        ProveQueue { slots: Arc::new(Semaphore::new(max_depth)), max_depth }
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// A slot held until the returned permit is dropped; `None` when full.
+    pub fn try_enter(&self) -> Option<OwnedSemaphorePermit> {
+        self.slots.clone().try_acquire_owned().ok()
+    }
+}
@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use risc0_zkvm::sha::Digest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::config::ProverMode;
+use crate::prover::sha256_hex;
+
+/// On-disk cache of finished proof responses, so a retried move (wallet
+/// signing failed, page reloaded) doesn't cost another Groth16 run.
+///
+/// Entries are keyed by SHA256(image id ‖ mode ‖ guest input), with the input
+/// in the guest's own serde encoding. Keys are one-way, but they are derived
+/// from the hidden board, so keep the directory as private as `receipts/`.
+#[derive(Clone)]
+pub struct ProofCache {
+    dir:         PathBuf,
+    max_entries: usize,            // oldest entries are evicted beyond this
+    max_age:     Option<Duration>, // entries older than this are treated as misses
+}
+
+impl ProofCache {
+    pub fn open(dir: impl Into<PathBuf>, max_entries: usize, max_age: Option<Duration>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        Ok(ProofCache { dir, max_entries, max_age })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Cache key for proving `image_id` on `input` in `mode`. The mode is part
+    /// of the key so a dev-mode result is never served to a real-proof host.
+    pub fn key<T: Serialize>(image_id: impl Into<Digest>, mode: ProverMode, input: &T) -> Result<String> {
+        let words = risc0_zkvm::serde::to_vec(input).context("Encoding cache key")?;
+        let mut bytes = image_id.into().as_bytes().to_vec();
+        bytes.extend_from_slice(mode.label().as_bytes());
+        bytes.extend(words.iter().flat_map(|w| w.to_le_bytes()));
+        Ok(sha256_hex(&bytes))
+    }
+
+    /// Cached response for `key`, if present and not expired. A corrupt or
+    /// unreadable entry is dropped and reported as a miss.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let path = self.path_for(key);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+
+        if self.is_expired(modified) {
+            debug!(key, "Cache entry expired");
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+
+        match fs::read(&path).map_err(anyhow::Error::from).and_then(|b| Ok(serde_json::from_slice(&b)?)) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                warn!(key, %error, "Dropping unreadable cache entry");
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    /// Store `value` under `key`, then evict expired and surplus entries.
+    pub fn put<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let path = self.path_for(key);
+        fs::write(&path, serde_json::to_vec(value)?).with_context(|| format!("Writing {}", path.display()))?;
+        self.evict()
+    }
+
+    fn evict(&self) -> Result<()> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir).with_context(|| format!("Reading {}", self.dir.display()))? {
+            let entry = entry?;
+            let modified = entry.metadata()?.modified()?;
+            if self.is_expired(modified) {
+                let _ = fs::remove_file(entry.path());
+            } else {
+                entries.push((modified, entry.path()));
+            }
+        }
+
+        if entries.len() > self.max_entries {
+            entries.sort();
+            let surplus = entries.len() - self.max_entries;
+            for (_, path) in entries.into_iter().take(surplus) {
+                let _ = fs::remove_file(path);
+            }
+            debug!(evicted = surplus, "Proof cache over capacity");
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        match self.max_age {
+            Some(max_age) => modified.elapsed().map(|age| age > max_age).unwrap_or(false),
+            None => false,
+        }
+    }
+
+    // Keys are always `key()` output, never client input.
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
@@ -0,0 +1,47 @@
+use serde::Serialize;
+
+// Nethermind RISC Zero Groth16 verifier the fog-of-chess contract calls
+pub const VERIFIER_ID: &str = "CBY3GOBGQXDGRR4K2KYJO2UOXDW5NRW6UKIQHUBNBNU2V3BXQBXGTVX7";
+
+/// How `/prove` produces receipts. Resolved once at startup and passed to
+/// every request, so the banner, `/status` and each proof always agree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProverMode {
+    /// Fake receipts and dev seals — fast, proves nothing
+    Dev,
+    /// Real Groth16 via the Bonsai proving service
+    Bonsai,
+    /// Real Groth16 on this machine (needs Docker for the Groth16 step)
+    LocalDocker,
+}
+
+impl ProverMode {
+    /// `RISC0_DEV_MODE` wins when set: truthy selects dev mode, anything else
+    /// forces real proofs. Unset, Bonsai credentials decide between Bonsai
+    /// and dev mode — a real local run always has to be asked for.
+    pub fn from_env() -> Self {
+        let bonsai_configured = std::env::var("BONSAI_API_KEY").is_ok()
+            && std::env::var("BONSAI_API_URL").is_ok();
+
+        match std::env::var("RISC0_DEV_MODE") {
+            Ok(v) if matches!(v.to_lowercase().as_str(), "1" | "true" | "yes") => ProverMode::Dev,
+            Ok(_) if bonsai_configured => ProverMode::Bonsai,
+            Ok(_) => ProverMode::LocalDocker,
+            Err(_) if bonsai_configured => ProverMode::Bonsai,
+            Err(_) => ProverMode::Dev,
+        }
+    }
+
+    pub fn is_dev(self) -> bool {
+        self == ProverMode::Dev
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProverMode::Dev         => "🟡 Dev mode (mock proofs)",
+            ProverMode::Bonsai      => "🟢 REAL Groth16 (Bonsai cloud)",
+            ProverMode::LocalDocker => "🟢 REAL Groth16 (local Docker)",
+        }
+    }
+}
@@ -0,0 +1,10 @@
+//! Lantern Chess ZK prover host — the `host` binary's modules, exposed as a
+//! library so the integration tests can drive the guest and the HTTP router.
+
+pub mod auth;
+pub mod cache;
+pub mod config;
+pub mod metrics;
+pub mod prover;
+pub mod receipts;
+pub mod server;
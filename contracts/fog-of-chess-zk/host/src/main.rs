@@ -1,164 +1,271 @@
-use axum::{routing::post, Json, Router};
-use risc0_zkvm::{default_prover, ExecutorEnv, ProverOpts, VerifierContext};
-use serde::{Deserialize, Serialize};
-use tower_http::cors::CorsLayer;
-use tokio::net::TcpListener;
-use risc0_zkvm::sha::Digest;
-use sha2::{Sha256, Digest as Sha2Digest};
-
-use methods::{METHOD_ELF, METHOD_ID};
-
-// Nethermind verifier selector — prepend to every Groth16 seal
-const GROTH16_SELECTOR: &str = "73c457ba";
-
-#[derive(Deserialize)]
-struct MoveRequest {
-    start_pos:  [u8; 2],
-    end_pos:    [u8; 2],
-    piece_type: u32,
-    salt:       u32,
-    commitment: String,
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+use risc0_zkvm::Receipt;
+
+use host::auth::{Auth, ProveQueue, RateLimiter};
+use host::cache::ProofCache;
+use host::config::ProverMode;
+use host::metrics::Metrics;
+use host::prover::{self, MoveRequest, PieceColor, ReceiptKind};
+use host::receipts::ReceiptStore;
+use host::server::{self, AppState, CorsPolicy, DEFAULT_CORS_ORIGINS};
+use tracing_subscriber::EnvFilter;
+
+/// Lantern Chess ZK prover — HTTP server and command-line tools.
+#[derive(Parser)]
+#[command(name = "host", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the HTTP prover server (default when no subcommand is given)
+    Serve(ServeArgs),
+    /// Prove a move request read from a JSON file (or stdin) and print the MoveResponse JSON
+    Prove {
+        /// Move request JSON; `-` or omitted reads from stdin
+        input: Option<PathBuf>,
+        /// Write the MoveResponse JSON here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Also save the full receipt as JSON, for `host verify`
+        #[arg(long)]
+        receipt: Option<PathBuf>,
+    },
+    /// Verify a saved receipt against this build's image id
+    Verify {
+        /// Receipt JSON written by `host prove --receipt`
+        receipt: PathBuf,
+    },
+    /// Print the guest image id (METHOD_ID) as hex
+    ImageId,
+    /// Compute the piece commitment SHA256(row ‖ col ‖ piece ‖ color ‖ salt) as hex
+    Commit {
+        row:   u8,
+        col:   u8,
+        /// Piece type, 1 knight … 6 king
+        piece: u8,
+        #[arg(value_enum)]
+        color: PieceColor,
+        salt:  u32,
+    },
+}
+
+#[derive(clap::Args)]
+struct ServeArgs {
+    /// Address to bind the HTTP server to
+    #[arg(long, default_value = "0.0.0.0", conflicts_with = "local_only")]
+    bind: IpAddr,
+    /// Bind to 127.0.0.1 only, so the hidden board never leaves this machine
+    #[arg(long)]
+    local_only: bool,
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 3001)]
+    port: u16,
+    /// Allowed CORS origin; repeat for several. Defaults to the Vite dev server
+    #[arg(long = "cors-origin")]
+    cors_origins: Vec<String>,
+    /// Allow requests from any origin (previous default)
+    #[arg(long, conflicts_with = "cors_origins")]
+    cors_any: bool,
+    /// Directory where full receipts are kept for POST /verify
+    #[arg(long, default_value = "receipts")]
+    receipts_dir: PathBuf,
+    /// Directory for cached proof responses, so retried moves skip proving
+    #[arg(long, default_value = "proof-cache")]
+    cache_dir: PathBuf,
+    /// Keep at most this many cached proofs; the oldest are evicted first
+    #[arg(long, default_value_t = 1024)]
+    cache_max_entries: usize,
+    /// Drop cached proofs older than this many seconds (0 = never expire)
+    #[arg(long, default_value_t = 7 * 24 * 60 * 60)]
+    cache_ttl_secs: u64,
+    /// Always prove from scratch
+    #[arg(long, conflicts_with_all = ["cache_dir", "cache_max_entries", "cache_ttl_secs"])]
+    no_cache: bool,
+    /// Require an `X-Api-Key` header matching a key in this file (one per line)
+    #[arg(long)]
+    api_keys_file: Option<PathBuf>,
+    /// Require requests signed with the player's Stellar key and tied to a session id
+    #[arg(long, requires = "allowed_accounts")]
+    signed_requests: bool,
+    /// Stellar accounts (G..., one per line) whose signed requests are accepted
+    #[arg(long, requires = "signed_requests")]
+    allowed_accounts: Option<PathBuf>,
+    /// Guarded requests allowed per client per minute (0 = unlimited)
+    #[arg(long, default_value_t = 30)]
+    rate_limit: u32,
+    /// Guarded requests allowed per minute across all clients (0 = unlimited)
+    #[arg(long, default_value_t = 300)]
+    global_rate_limit: u32,
+    /// Proving requests allowed in flight at once; more get 503
+    #[arg(long, default_value_t = 4)]
+    max_queue: usize,
 }
 
-#[derive(Serialize)]
-struct MoveResponse {
-    seal:          String, // selector(4 bytes) + groth16 proof — ready for Nethermind verifier
-    journal:       String, // hex of raw journal bytes (end_pos)
-    journal_sha256:String, // sha256 of journal — what Nethermind verifier expects
-    image_id:      String, // METHOD_ID hex — identifies your circuit
-    is_dev_mode:   bool,   // tells frontend if this is a real proof
+impl Default for ServeArgs {
+    fn default() -> Self {
+        ServeArgs {
+            bind:              IpAddr::from([0, 0, 0, 0]),
+            local_only:        false,
+            port:              3001,
+            cors_origins:      Vec::new(),
+            cors_any:          false,
+            receipts_dir:      PathBuf::from("receipts"),
+            cache_dir:         PathBuf::from("proof-cache"),
+            cache_max_entries: 1024,
+            cache_ttl_secs:    7 * 24 * 60 * 60,
+            no_cache:          false,
+            api_keys_file:     None,
+            signed_requests:   false,
+            allowed_accounts:  None,
+            rate_limit:        30,
+            global_rate_limit: 300,
+            max_queue:         4,
+        }
+    }
 }
 
-#[derive(Serialize)]
-struct ErrorResponse {
-    error: String,
+fn read_input(input: Option<PathBuf>) -> Result<String> {
+    match input {
+        Some(path) if path.as_os_str() != "-" => {
+            fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))
+        }
+        _ => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf).context("Reading stdin")?;
+            Ok(buf)
+        }
+    }
+}
+
+fn prove(input: Option<PathBuf>, output: Option<PathBuf>, receipt_path: Option<PathBuf>) -> Result<()> {
+    let request: MoveRequest = serde_json::from_str(&read_input(input)?).context("Invalid move request JSON")?;
+    let (response, receipt, stats) = prover::prove_move(&request, ProverMode::from_env())?;
+    tracing::info!(total_cycles = stats.total_cycles, segments = stats.segments, wall_time = ?stats.wall_time);
+
+    if let Some(path) = receipt_path {
+        fs::write(&path, serde_json::to_vec(&receipt)?)
+            .with_context(|| format!("Writing {}", path.display()))?;
+    }
+
+    let json = serde_json::to_string_pretty(&response)?;
+    match output {
+        Some(path) => fs::write(&path, json).with_context(|| format!("Writing {}", path.display()))?,
+        None => writeln!(io::stdout(), "{json}")?,
+    }
+    Ok(())
 }
 
-async fn prove_move(Json(payload): Json<MoveRequest>) -> Json<MoveResponse> {
-    let commitment_bytes = hex::decode(&payload.commitment).expect("Invalid hex commitment");
-    let mut commitment: [u8; 32] = [0u8; 32];
-    commitment.copy_from_slice(&commitment_bytes);
-
-    let env = ExecutorEnv::builder()
-        .write(&(payload.start_pos, payload.end_pos, payload.piece_type, payload.salt, commitment))
-        .unwrap()
-        .build()
-        .unwrap();
-
-    let prover = default_prover();
-
-    // Check if Bonsai is configured — if so, use Groth16. Otherwise fall back to dev mode.
-    let bonsai_configured = std::env::var("BONSAI_API_KEY").is_ok()
-        && std::env::var("BONSAI_API_URL").is_ok();
-    let dev_mode = std::env::var("RISC0_DEV_MODE").map(|v| v == "1").unwrap_or(!bonsai_configured);
-
-    let image_id_hex = Digest::from(METHOD_ID).to_string();
-
-    if !dev_mode {
-        // ── REAL GROTH16 PROOF (Bonsai or local Docker) ───────────────────────
-        let prove_info = prover
-            .prove_with_ctx(
-                env,
-                &VerifierContext::default(),
-                METHOD_ELF,
-                &ProverOpts::groth16(), // ← request Groth16 specifically
-            )
-            .expect("Proving failed");
-
-        let receipt = prove_info.receipt;
-
-        // Extract Groth16 seal
-        let groth16 = receipt
-            .inner
-            .groth16()
-            .expect("Expected Groth16 receipt — make sure Bonsai or Docker is configured");
-
-        let seal_bytes    = &groth16.seal;
-        let seal_hex      = hex::encode(seal_bytes);
-        // Prepend Nethermind verifier selector
-        let seal_with_selector = format!("{}{}", GROTH16_SELECTOR, seal_hex);
-
-        let journal_bytes = &receipt.journal.bytes;
-        let journal_hex   = hex::encode(journal_bytes);
-
-        // SHA256 of journal — what Nethermind verifier's `journal` param expects
-        let mut hasher = Sha256::new();
-        hasher.update(journal_bytes);
-        let journal_sha256 = hex::encode(hasher.finalize());
-
-        println!("✅ Real Groth16 proof generated");
-        println!("   image_id:       {}", image_id_hex);
-        println!("   journal:        {}", journal_hex);
-        println!("   journal_sha256: {}", journal_sha256);
-        println!("   seal (partial): {}...", &seal_with_selector[..20]);
-
-        Json(MoveResponse {
-            seal:          seal_with_selector,
-            journal:       journal_hex,
-            journal_sha256,
-            image_id:      image_id_hex,
-            is_dev_mode:   false,
-        })
-    } else {
-        // ── DEV MODE — fast mock proof for local development ─────────────────
-        println!("⚠️  DEV MODE — mock proof (set BONSAI_API_KEY + BONSAI_API_URL for real proofs)");
-
-        let prove_info = prover.prove(env, METHOD_ELF).expect("Dev prove failed");
-        let receipt    = prove_info.receipt;
-
-        let journal_bytes  = &receipt.journal.bytes;
-        let journal_hex    = hex::encode(journal_bytes);
-
-        let mut hasher = Sha256::new();
-        hasher.update(journal_bytes);
-        let journal_sha256 = hex::encode(hasher.finalize());
-
-        // Mock seal — journal bytes padded, prefixed with selector so format matches
-        let mock_seal_bytes = {
-            let mut v = journal_bytes.to_vec();
-            v.resize(256, 0); // pad to look like a real seal
-            v
+fn verify(receipt_path: PathBuf) -> Result<()> {
+    let bytes = fs::read(&receipt_path).with_context(|| format!("Reading {}", receipt_path.display()))?;
+    let receipt: Receipt = serde_json::from_slice(&bytes).context("Invalid receipt JSON")?;
+
+    match prover::verify_receipt(&receipt, ReceiptKind::Move, ProverMode::from_env()) {
+        Ok(verified) => {
+            println!("✅ Receipt valid for image id {}", prover::image_id_hex());
+            println!("   end_pos:        {:?}", verified.end_pos);
+            println!("   journal:        {}", verified.journal);
+            println!("   journal_sha256: {}", verified.journal_sha256);
+            Ok(())
+        }
+        Err(e) => bail!("❌ {e:#}"),
+    }
+}
+
+impl ServeArgs {
+    fn addr(&self) -> SocketAddr {
+        let ip = if self.local_only { IpAddr::from([127, 0, 0, 1]) } else { self.bind };
+        SocketAddr::new(ip, self.port)
+    }
+
+    fn cors(&self) -> CorsPolicy {
+        if self.cors_any {
+            CorsPolicy::AnyOrigin
+        } else if self.cors_origins.is_empty() {
+            CorsPolicy::Origins(DEFAULT_CORS_ORIGINS.iter().map(|o| o.to_string()).collect())
+        } else {
+            CorsPolicy::Origins(self.cors_origins.clone())
+        }
+    }
+
+    fn cache(&self) -> Result<Option<ProofCache>> {
+        if self.no_cache {
+            return Ok(None);
+        }
+        let max_age = (self.cache_ttl_secs > 0).then(|| Duration::from_secs(self.cache_ttl_secs));
+        Ok(Some(ProofCache::open(&self.cache_dir, self.cache_max_entries, max_age)?))
+    }
+
+    fn auth(&self) -> Result<Auth> {
+        let api_keys = match &self.api_keys_file {
+            Some(path) => {
+                let keys = Auth::load_api_keys(path)?;
+                if keys.is_empty() {
+                    bail!("{} has no API keys", path.display());
+                }
+                keys
+            }
+            None => HashSet::new(),
+        };
+        let allowed_accounts = match &self.allowed_accounts {
+            Some(path) => {
+                let accounts = Auth::load_accounts(path)?;
+                if accounts.is_empty() {
+                    bail!("{} has no accounts", path.display());
+                }
+                accounts
+            }
+            None => HashSet::new(),
         };
-        let seal_with_selector = format!("{}{}", GROTH16_SELECTOR, hex::encode(&mock_seal_bytes));
-
-        Json(MoveResponse {
-            seal:          seal_with_selector,
-            journal:       journal_hex,
-            journal_sha256,
-            image_id:      image_id_hex,
-            is_dev_mode:   true,
-        })
+        Ok(Auth::new(api_keys, allowed_accounts))
     }
 }
 
 #[tokio::main]
-async fn main() {
-    let bonsai_ready = std::env::var("BONSAI_API_KEY").is_ok();
-    let dev_mode_env  = std::env::var("RISC0_DEV_MODE").unwrap_or_default();
-    let real_mode     = !bonsai_ready && dev_mode_env == "0";
-    let mode_label    = if bonsai_ready { "🟢 REAL Groth16 (Bonsai cloud)" }
-                        else if real_mode { "🟢 REAL Groth16 (local Docker)" }
-                        else { "🟡 Dev mode (mock proofs)" };
-
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  LANTERN CHESS — ZK PROVER SERVER");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Mode:      {}", mode_label);
-    println!("  Image ID:  {}", Digest::from(METHOD_ID).to_string());
-    println!("  Verifier:  CBY3GOBGQXDGRR4K2KYJO2UOXDW5NRW6UKIQHUBNBNU2V3BXQBXGTVX7");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-
-    if !bonsai_ready && !real_mode {
-        println!("  To enable real proofs:");
-        println!("  RISC0_DEV_MODE=0 cargo run --release  (uses local Docker)");
-        println!("  or set BONSAI_API_KEY + BONSAI_API_URL for cloud proving");
-    }
+async fn main() -> Result<()> {
+    // Logs go to stderr so `host prove` can write its JSON to stdout.
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with_writer(io::stderr)
+        .init();
 
-    let app = Router::new()
-        .route("/prove", post(prove_move))
-        .layer(CorsLayer::permissive());
+    let cli = Cli::parse();
 
-    let listener = TcpListener::bind("0.0.0.0:3001").await.unwrap();
-    println!("\n🚀 Prover running on http://localhost:3001/prove\n");
-    axum::serve(listener, app).await.unwrap();
-}
\ No newline at end of file
+    match cli.command.unwrap_or(Command::Serve(ServeArgs::default())) {
+        Command::Serve(args) => {
+            let state = AppState {
+                mode:     ProverMode::from_env(),
+                receipts: ReceiptStore::open(&args.receipts_dir)?,
+                cache:    args.cache()?,
+                auth:     Arc::new(args.auth()?),
+                limiter:  RateLimiter::new(args.rate_limit).with_global_limit(args.global_rate_limit),
+                queue:    ProveQueue::new(args.max_queue.max(1)),
+                metrics:  Metrics::default(),
+            };
+            server::serve(args.addr(), state, &args.cors()).await
+        }
+        Command::Prove { input, output, receipt } => {
+            tokio::task::spawn_blocking(move || prove(input, output, receipt)).await?
+        }
+        Command::Verify { receipt } => verify(receipt),
+        Command::ImageId => {
+            println!("{}", prover::image_id_hex());
+            Ok(())
+        }
+        Command::Commit { row, col, piece, color, salt } => {
+            println!("{}", hex::encode(prover::compute_commitment([row, col], piece, color, salt)));
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::config::ProverMode;
+use crate::prover::ProofStats;
+
+// Upper bounds (seconds) of the wall-time histogram: dev-mode proofs land in
+// the first buckets, local Groth16 runs in the last ones.
+const WALL_TIME_BUCKETS: &[f64] = &[0.05, 0.25, 1.0, 5.0, 15.0, 60.0, 180.0, 300.0, 600.0];
+
+// (name, type, help, value) for every per-series counter and gauge
+type SeriesMetric = (&'static str, &'static str, &'static str, fn(&Series) -> u64);
+
+const SERIES_METRICS: &[SeriesMetric] = &[
+    ("prover_total_cycles_total", "counter", "zkVM cycles executed, including po2 padding.", |s| s.total_cycles),
+    ("prover_user_cycles_total", "counter", "zkVM cycles spent in guest code.", |s| s.user_cycles),
+    ("prover_segments_total", "counter", "zkVM segments executed.", |s| s.segments),
+    ("prover_last_total_cycles", "gauge", "Total cycles of the most recent run.", |s| s.last_total_cycles),
+    ("prover_last_user_cycles", "gauge", "User cycles of the most recent run.", |s| s.last_user_cycles),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    Ok,
+    Cached,
+    Rejected, // /validate ran the guest and it refused the move
+    Failed,
+}
+
+impl Outcome {
+    fn label(self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Cached => "cached",
+            Outcome::Rejected => "rejected",
+            Outcome::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Series {
+    total_cycles:      u64,
+    user_cycles:       u64,
+    segments:          u64,
+    last_total_cycles: u64, // latest run — the number to watch for guest regressions
+    last_user_cycles:  u64,
+    wall_time_sum:     f64,
+    wall_time_count:   u64,
+    wall_time_buckets: Vec<u64>, // cumulative counts, one per WALL_TIME_BUCKETS entry
+}
+
+#[derive(Default)]
+struct Registry {
+    requests: BTreeMap<(&'static str, &'static str, Outcome), u64>, // (endpoint, mode, outcome)
+    series:   BTreeMap<(&'static str, &'static str), Series>,      // (endpoint, mode)
+}
+
+/// Per-endpoint counters for GET /metrics, in Prometheus text format.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Mutex<Registry>>,
+}
+
+fn mode_label(mode: ProverMode) -> &'static str {
+    match mode {
+        ProverMode::Dev => "dev",
+        ProverMode::Bonsai => "bonsai",
+        ProverMode::LocalDocker => "local_docker",
+    }
+}
+
+impl Metrics {
+    fn registry(&self) -> std::sync::MutexGuard<'_, Registry> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count a request without execution figures (cache hit, rejection, failure).
+    pub fn record_outcome(&self, endpoint: &'static str, mode: ProverMode, outcome: Outcome) {
+        *self.registry().requests.entry((endpoint, mode_label(mode), outcome)).or_default() += 1;
+    }
+
+    /// Count a successful request along with its cycles and wall time.
+    pub fn record(&self, endpoint: &'static str, mode: ProverMode, stats: &ProofStats) {
+        let mut registry = self.registry();
+        *registry.requests.entry((endpoint, mode_label(mode), Outcome::Ok)).or_default() += 1;
+
+        let series = registry.series.entry((endpoint, mode_label(mode))).or_default();
+        series.total_cycles += stats.total_cycles;
+        series.user_cycles += stats.user_cycles;
+        series.segments += stats.segments as u64;
+        series.last_total_cycles = stats.total_cycles;
+        series.last_user_cycles = stats.user_cycles;
+
+        let seconds = stats.wall_time.as_secs_f64();
+        series.wall_time_sum += seconds;
+        series.wall_time_count += 1;
+        series.wall_time_buckets.resize(WALL_TIME_BUCKETS.len(), 0);
+        for (count, bound) in series.wall_time_buckets.iter_mut().zip(WALL_TIME_BUCKETS) {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let registry = self.registry();
+        let mut out = String::new();
+
+        out.push_str("# HELP prover_requests_total Requests handled, by endpoint, prover mode and outcome.\n");
+        out.push_str("# TYPE prover_requests_total counter\n");
+        for ((endpoint, mode, outcome), count) in &registry.requests {
+            let _ = writeln!(
+                out,
+                "prover_requests_total{{endpoint=\"{endpoint}\",mode=\"{mode}\",outcome=\"{}\"}} {count}",
+                outcome.label()
+            );
+        }
+
+        for &(name, kind, help, value) in SERIES_METRICS {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            for ((endpoint, mode), series) in &registry.series {
+                let _ = writeln!(out, "{name}{{endpoint=\"{endpoint}\",mode=\"{mode}\"}} {}", value(series));
+            }
+        }
+
+        out.push_str("# HELP prover_wall_time_seconds Wall time per request, all proving runs included.\n");
+        out.push_str("# TYPE prover_wall_time_seconds histogram\n");
+        for ((endpoint, mode), series) in &registry.series {
+            let labels = format!("endpoint=\"{endpoint}\",mode=\"{mode}\"");
+            for (bound, count) in WALL_TIME_BUCKETS.iter().zip(&series.wall_time_buckets) {
+                let _ = writeln!(out, "prover_wall_time_seconds_bucket{{{labels},le=\"{bound}\"}} {count}");
+            }
+            let _ = writeln!(out, "prover_wall_time_seconds_bucket{{{labels},le=\"+Inf\"}} {}", series.wall_time_count);
+            let _ = writeln!(out, "prover_wall_time_seconds_sum{{{labels}}} {}", series.wall_time_sum);
+            let _ = writeln!(out, "prover_wall_time_seconds_count{{{labels}}} {}", series.wall_time_count);
+        }
+        out
+    }
+}
@@ -0,0 +1,534 @@
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use fog_chess_core::journal::{decode_batch_journal, ReplayJournal};
+use fog_chess_core::{is_legal_move, is_legal_shape, square_commitment, Board, Color, Piece, Square};
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::{
+    default_executor, default_prover, BonsaiProver, ExecutorEnv, ExitCode, ProveInfo, Prover, ProverOpts,
+    Receipt, VerifierContext,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as Sha2Digest, Sha256};
+use tracing::{info, warn};
+
+use methods::{BATCH_ELF, BATCH_ID, METHOD_ELF, METHOD_ID, REPLAY_ELF, REPLAY_ID};
+
+use crate::config::ProverMode;
+
+// Nethermind verifier selector — prepend to every Groth16 seal
+const GROTH16_SELECTOR: &str = "73c457ba";
+
+// Dev-mode seals get their own selector (b"DEV!") so no verifier can mistake
+// them for Groth16. Must match DEV_SELECTOR in the fog-of-chess contract.
+const DEV_SEAL_SELECTOR: &str = "44455621";
+
+// Upper bound on moves per batch proof. Must match MAX_BATCH_MOVES in the
+// fog-of-chess contract.
+pub const MAX_BATCH_MOVES: usize = 32;
+
+/// Side the moving piece belongs to — pawns need it to know which way is forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum PieceColor {
+    White,
+    Black,
+}
+
+impl PieceColor {
+    /// The code the move guest reads: 0 white, 1 black.
+    pub fn code(self) -> u8 {
+        Color::from(self).code()
+    }
+}
+
+impl From<PieceColor> for Color {
+    fn from(color: PieceColor) -> Color {
+        match color {
+            PieceColor::White => Color::White,
+            PieceColor::Black => Color::Black,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MoveRequest {
+    pub start_pos:  [u8; 2],
+    pub end_pos:    [u8; 2],
+    pub piece_type: u32,
+    pub color:      PieceColor,
+    pub salt:       u32,
+    pub commitment: String,
+}
+
+// start_pos, piece_type and salt are the player's hidden board — never let
+// them reach a log line. end_pos and the commitment are public anyway.
+impl fmt::Debug for MoveRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MoveRequest")
+            .field("start_pos", &"<redacted>")
+            .field("end_pos", &self.end_pos)
+            .field("piece_type", &"<redacted>")
+            .field("color", &self.color)
+            .field("salt", &"<redacted>")
+            .field("commitment", &self.commitment)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MoveResponse {
+    pub seal:          String, // selector(4 bytes) + groth16 proof — ready for Nethermind verifier (dev: "DEV!" + journal_sha256)
+    pub journal:       String, // hex of raw journal bytes (end_pos, color) — what verify_move takes
+    pub journal_sha256:String, // sha256 of journal — what Nethermind verifier expects; the contract recomputes it
+    pub image_id:      String, // METHOD_ID hex — identifies your circuit
+    pub is_dev_mode:   bool,   // tells frontend if this is a real proof
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_id:    Option<String>, // id of the stored full receipt, for POST /verify
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BatchResponse {
+    pub seal:          String, // same seal format as MoveResponse, over the batch journal
+    pub journal:       String, // hex of move_image_id(32) ‖ color(1) ‖ end_pos(2) per move
+    pub journal_sha256:String,
+    pub image_id:      String, // BATCH_ID hex — the aggregation circuit
+    pub move_image_id: String, // METHOD_ID hex — circuit every batched move was proven with
+    pub moves:         usize,
+    pub is_dev_mode:   bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_id:    Option<String>,
+}
+
+/// One replayed move: (from, to) as [row, col].
+pub type ReplayMove = ([u8; 2], [u8; 2]);
+
+/// A finished game, revealed by both players for a replay proof.
+#[derive(Deserialize)]
+pub struct ReplayRequest {
+    pub white_board: Vec<u8>, // 64 squares, row * 8 + col, 0 = empty, else piece type
+    pub white_salt:  u32,     // salt of player1's board commitment
+    pub black_board: Vec<u8>,
+    pub black_salt:  u32,     // salt of player2's board commitment
+    pub moves:       Vec<ReplayMove>, // in play order, white first
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReplayResponse {
+    pub seal:             String, // same seal format as MoveResponse, over the replay journal
+    pub journal:          String, // hex of ReplayJournal: both commitments, both move chains, plies, result
+    pub journal_sha256:   String,
+    pub image_id:         String, // REPLAY_ID hex
+    pub white_commitment: String,
+    pub black_commitment: String,
+    pub white_moves:      String, // move chains the contract checks against the moves it verified
+    pub black_moves:      String,
+    pub plies:            u32,
+    pub result:           u8,     // 0 = undecided, 1 = white (player1) won, 2 = black (player2) won
+    pub is_dev_mode:      bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt_id:       Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ValidateResponse {
+    pub legal:          bool,
+    pub reason:         Option<String>,  // guest panic message when the move is rejected
+    pub end_pos:        Option<[u8; 2]>, // decoded journal
+    pub journal:        Option<String>,  // hex of the journal /prove would produce
+    pub journal_sha256: Option<String>,
+    pub total_cycles:   Option<u64>,
+    pub user_cycles:    Option<u64>,
+    pub segments:       Option<usize>,
+}
+
+impl ValidateResponse {
+    pub(crate) fn rejected(reason: String) -> Self {
+        ValidateResponse {
+            legal:          false,
+            reason:         Some(reason),
+            end_pos:        None,
+            journal:        None,
+            journal_sha256: None,
+            total_cycles:   None,
+            user_cycles:    None,
+            segments:       None,
+        }
+    }
+}
+
+/// Execution figures for one request, reported on GET /metrics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProofStats {
+    pub total_cycles: u64,      // including po2 padding — what proving time scales with
+    pub user_cycles:  u64,
+    pub segments:     usize,
+    pub wall_time:    Duration, // whole request, every proving run included
+}
+
+impl ProofStats {
+    fn add(&mut self, info: &ProveInfo) {
+        self.total_cycles += info.stats.total_cycles;
+        self.user_cycles += info.stats.user_cycles;
+        self.segments += info.stats.segments;
+    }
+}
+
+/// Which guest a receipt came from. Stored with every receipt so /verify
+/// checks it against the right image id and decodes the right journal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptKind {
+    #[default]
+    Move,
+    Batch,
+    Replay,
+}
+
+impl ReceiptKind {
+    pub fn image_id(self) -> [u32; 8] {
+        match self {
+            ReceiptKind::Move => METHOD_ID,
+            ReceiptKind::Batch => BATCH_ID,
+            ReceiptKind::Replay => REPLAY_ID,
+        }
+    }
+}
+
+/// Result of a successful `verify_receipt`. Which of the decoded fields are
+/// set depends on the kind: `end_pos` for a move, `end_positions` for a
+/// batch, `plies` and `result` for a replay.
+pub struct VerifiedReceipt {
+    pub end_pos:        Option<[u8; 2]>,
+    pub end_positions:  Option<Vec<[u8; 2]>>,
+    pub plies:          Option<u32>,
+    pub result:         Option<u8>,
+    pub journal:        String,
+    pub journal_sha256: String,
+}
+
+pub fn image_id_hex() -> String {
+    Digest::from(METHOD_ID).to_string()
+}
+
+pub fn parse_commitment(commitment_hex: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(commitment_hex).map_err(|e| format!("Invalid hex commitment: {e}"))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("Commitment must be 32 bytes, got {}", b.len()))
+}
+
+/// Same hash the guest checks: SHA256(row ‖ col ‖ piece ‖ color ‖ salt as big-endian u32).
+pub fn compute_commitment(pos: [u8; 2], piece_type: u8, color: PieceColor, salt: u32) -> [u8; 32] {
+    square_commitment(pos, piece_type, color.code(), salt)
+}
+
+/// The move guest's checks, run natively so a bad move is turned away before
+/// it costs a zkVM run. Messages match the guest's panics.
+pub fn precheck_move(payload: &MoveRequest) -> Result<(), String> {
+    let piece_code = u8::try_from(payload.piece_type).map_err(|_| "Unknown piece type!".to_string())?;
+    let commitment = compute_commitment(payload.start_pos, piece_code, payload.color, payload.salt);
+    if commitment != parse_commitment(&payload.commitment)? {
+        return Err("Commitment verification failed!".into());
+    }
+    let (Some(from), Some(to)) = (Square::from_pos(payload.start_pos), Square::from_pos(payload.end_pos)) else {
+        return Err("Square off the board!".into());
+    };
+    let piece = Piece::from_code(piece_code);
+    if !piece.is_some_and(|piece| is_legal_shape(piece, payload.color.into(), from, to)) {
+        return Err("Illegal move for this piece type!".into());
+    }
+    Ok(())
+}
+
+/// Replay the game natively under the same rules as the replay guest, so an
+/// illegal game is reported with its ply instead of failing mid-proof.
+pub fn precheck_replay(payload: &ReplayRequest) -> Result<(), String> {
+    let mut board = Board::decode(&payload.white_board, &payload.black_board)
+        .map_err(|e| format!("Invalid board: {e}"))?;
+    let mut side = Color::White;
+    let mut game_over = false;
+    for (ply, &(from, to)) in payload.moves.iter().enumerate() {
+        if game_over {
+            return Err(format!("Move {} played after the king was captured", ply + 1));
+        }
+        let legal = match (Square::from_pos(from), Square::from_pos(to)) {
+            (Some(from), Some(to)) if is_legal_move(&board, side, from, to) => {
+                game_over = matches!(board.apply(from, to), Some((_, Piece::King)));
+                true
+            }
+            _ => false,
+        };
+        if !legal {
+            return Err(format!("Illegal move {} ({from:?} -> {to:?}) for {side:?}", ply + 1));
+        }
+        side = side.opposite();
+    }
+    Ok(())
+}
+
+/// Exactly what the move guest reads: (start_pos, end_pos, piece_type, color, salt, commitment).
+pub type MoveInput = ([u8; 2], [u8; 2], u32, u8, u32, [u8; 32]);
+
+pub fn move_input(payload: &MoveRequest) -> Result<MoveInput, String> {
+    let commitment = parse_commitment(&payload.commitment)?;
+    Ok((payload.start_pos, payload.end_pos, payload.piece_type, payload.color.code(), payload.salt, commitment))
+}
+
+/// Exactly what the replay guest reads.
+pub fn replay_input(payload: &ReplayRequest) -> (&[u8], u32, &[u8], u32, &[ReplayMove]) {
+    (&payload.white_board, payload.white_salt, &payload.black_board, payload.black_salt, &payload.moves)
+}
+
+fn build_env(payload: &MoveRequest) -> Result<ExecutorEnv<'static>> {
+    let input = move_input(payload).map_err(|e| anyhow!(e))?;
+    ExecutorEnv::builder().write(&input)?.build()
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hex::encode(hasher.finalize())
+}
+
+/// Run the guest in the executor only — no proof is generated, so this answers
+/// "is this move legal?" in milliseconds instead of a full proving run.
+pub fn validate_move(payload: &MoveRequest) -> Result<ValidateResponse> {
+    let env = build_env(payload)?;
+
+    // The guest asserts on a bad commitment or an illegal move, so a rejected
+    // move surfaces as an execution error carrying the panic message.
+    let session = match default_executor().execute(env, METHOD_ELF) {
+        Ok(session) => session,
+        Err(e) => return Ok(ValidateResponse::rejected(e.to_string())),
+    };
+
+    if session.exit_code != ExitCode::Halted(0) {
+        return Ok(ValidateResponse::rejected(format!("Guest exited with {:?}", session.exit_code)));
+    }
+
+    let journal_bytes = &session.journal.bytes;
+    let (end_pos, _color): ([u8; 2], u8) = session.journal.decode().context("Unexpected journal")?;
+
+    // Executor segments report user cycles; total cycles include po2 padding,
+    // which is what proving time actually scales with.
+    let total_cycles = session.segments.iter().map(|s| 1u64 << s.po2).sum();
+
+    Ok(ValidateResponse {
+        legal:          true,
+        reason:         None,
+        end_pos:        Some(end_pos),
+        journal:        Some(hex::encode(journal_bytes)),
+        journal_sha256: Some(sha256_hex(journal_bytes)),
+        total_cycles:   Some(total_cycles),
+        user_cycles:    Some(session.cycles()),
+        segments:       Some(session.segments.len()),
+    })
+}
+
+fn prover_for(mode: ProverMode) -> Rc<dyn Prover> {
+    match mode {
+        ProverMode::Bonsai => Rc::new(BonsaiProver::new("bonsai")),
+        _ => default_prover(),
+    }
+}
+
+// dev_mode is set explicitly on every ProverOpts so the outcome depends
+// only on `mode`, not on whatever RISC0_DEV_MODE happens to be.
+fn final_opts(mode: ProverMode) -> ProverOpts {
+    if mode.is_dev() {
+        ProverOpts::default().with_dev_mode(true)
+    } else {
+        ProverOpts::groth16().with_dev_mode(false) // ← request Groth16 specifically
+    }
+}
+
+/// Prove `elf` and build the seal the contract expects for `mode`.
+/// Proving stats are added to `stats`.
+fn prove_for_chain(env: ExecutorEnv<'_>, elf: &[u8], mode: ProverMode, stats: &mut ProofStats) -> Result<(String, Receipt)> {
+    let ctx = VerifierContext::default().with_dev_mode(mode.is_dev());
+    let info = prover_for(mode)
+        .prove_with_ctx(env, &ctx, elf, &final_opts(mode))
+        .context("Proving failed")?;
+    stats.add(&info);
+    let receipt = info.receipt;
+
+    let seal = if mode.is_dev() {
+        // ── DEV MODE — fast mock proof for local development ─────────────────
+        warn!("DEV MODE — mock proof (set BONSAI_API_KEY + BONSAI_API_URL for real proofs)");
+
+        // Dev seal — selector(4) + sha256(journal)(32). 36 bytes, so it can't
+        // pass for a 260-byte Groth16 seal; the contract only accepts it when
+        // an admin has enabled its dev verifier.
+        format!("{}{}", DEV_SEAL_SELECTOR, sha256_hex(&receipt.journal.bytes))
+    } else {
+        // ── REAL GROTH16 PROOF (Bonsai or local Docker) ───────────────────────
+        let groth16 = receipt
+            .inner
+            .groth16()
+            .context("Expected Groth16 receipt — make sure Bonsai or Docker is configured")?;
+
+        // Prepend Nethermind verifier selector
+        format!("{}{}", GROTH16_SELECTOR, hex::encode(&groth16.seal))
+    };
+    Ok((seal, receipt))
+}
+
+/// Prove a move and return both the frontend-facing response and the full
+/// receipt, so callers can keep the receipt for later local verification.
+pub fn prove_move(payload: &MoveRequest, mode: ProverMode) -> Result<(MoveResponse, Receipt, ProofStats)> {
+    let started = Instant::now();
+    let mut stats = ProofStats::default();
+    let env = build_env(payload)?;
+
+    let image_id_hex = image_id_hex();
+    let (seal, receipt) = prove_for_chain(env, METHOD_ELF, mode, &mut stats)?;
+
+    let journal_bytes = &receipt.journal.bytes;
+    // SHA256 of journal — what Nethermind verifier's `journal` param expects
+    let journal_sha256 = sha256_hex(journal_bytes);
+
+    if !mode.is_dev() {
+        info!(image_id = %image_id_hex, %journal_sha256, "Real Groth16 proof generated");
+    }
+
+    let response = MoveResponse {
+        seal,
+        journal:       hex::encode(journal_bytes),
+        journal_sha256,
+        image_id:      image_id_hex,
+        is_dev_mode:   mode.is_dev(),
+        receipt_id:    None,
+    };
+    stats.wall_time = started.elapsed();
+    Ok((response, receipt, stats))
+}
+
+/// Prove several moves as one receipt. Each move is proven to a succinct
+/// receipt first and handed to the batch guest as an assumption; only the
+/// final batch receipt is compressed to Groth16, so the contract verifies
+/// one seal for the whole sequence.
+pub fn prove_batch(payloads: &[MoveRequest], mode: ProverMode) -> Result<(BatchResponse, Receipt, ProofStats)> {
+    if payloads.is_empty() || payloads.len() > MAX_BATCH_MOVES {
+        bail!("A batch needs between 1 and {MAX_BATCH_MOVES} moves, got {}", payloads.len());
+    }
+    if payloads.iter().any(|p| p.color != payloads[0].color) {
+        bail!("A batch holds one side's moves, not both");
+    }
+
+    let started = Instant::now();
+    let mut stats = ProofStats::default();
+    let prover = prover_for(mode);
+    let ctx = VerifierContext::default().with_dev_mode(mode.is_dev());
+    let succinct = ProverOpts::succinct().with_dev_mode(mode.is_dev());
+
+    let mut builder = ExecutorEnv::builder();
+    let mut journals = Vec::with_capacity(payloads.len());
+    for (ply, payload) in payloads.iter().enumerate() {
+        let env = build_env(payload).with_context(|| format!("Move {ply}"))?;
+        let info = prover
+            .prove_with_ctx(env, &ctx, METHOD_ELF, &succinct)
+            .with_context(|| format!("Proving move {ply} failed"))?;
+        stats.add(&info);
+        let receipt = info.receipt;
+        journals.push(receipt.journal.bytes.clone());
+        builder.add_assumption(receipt);
+    }
+
+    let env = builder.write(&(METHOD_ID, journals))?.build()?;
+    let (seal, receipt) = prove_for_chain(env, BATCH_ELF, mode, &mut stats)?;
+
+    let journal_bytes = &receipt.journal.bytes;
+    let journal_sha256 = sha256_hex(journal_bytes);
+    info!(moves = payloads.len(), %journal_sha256, is_dev_mode = mode.is_dev(), "Batch proof generated");
+
+    let response = BatchResponse {
+        seal,
+        journal:       hex::encode(journal_bytes),
+        journal_sha256,
+        image_id:      Digest::from(BATCH_ID).to_string(),
+        move_image_id: image_id_hex(),
+        moves:         payloads.len(),
+        is_dev_mode:   mode.is_dev(),
+        receipt_id:    None,
+    };
+    stats.wall_time = started.elapsed();
+    Ok((response, receipt, stats))
+}
+
+/// Prove a whole finished game: both boards match their commitments and
+/// every move was legal. Lets the contract settle a disputed result.
+pub fn prove_replay(payload: &ReplayRequest, mode: ProverMode) -> Result<(ReplayResponse, Receipt, ProofStats)> {
+    let started = Instant::now();
+    let mut stats = ProofStats::default();
+    if payload.white_board.len() != 64 || payload.black_board.len() != 64 {
+        bail!("Boards must have 64 squares");
+    }
+
+    let env = ExecutorEnv::builder().write(&replay_input(payload))?.build()?;
+    let (seal, receipt) = prove_for_chain(env, REPLAY_ELF, mode, &mut stats)?;
+
+    let journal_bytes = &receipt.journal.bytes;
+    let journal = ReplayJournal::decode(journal_bytes).map_err(|err| anyhow!("Malformed replay journal: {err}"))?;
+    let journal_sha256 = sha256_hex(journal_bytes);
+    let (plies, result) = (journal.plies, journal.result as u8);
+    info!(plies, result, %journal_sha256, is_dev_mode = mode.is_dev(), "Replay proof generated");
+
+    let response = ReplayResponse {
+        seal,
+        journal:          hex::encode(journal_bytes),
+        journal_sha256,
+        image_id:         Digest::from(REPLAY_ID).to_string(),
+        white_commitment: hex::encode(journal.white_commitment),
+        black_commitment: hex::encode(journal.black_commitment),
+        white_moves:      hex::encode(journal.white_moves),
+        black_moves:      hex::encode(journal.black_moves),
+        plies,
+        result,
+        is_dev_mode:      mode.is_dev(),
+        receipt_id:       None,
+    };
+    stats.wall_time = started.elapsed();
+    Ok((response, receipt, stats))
+}
+
+/// Check a receipt against this build's image id for `kind` and decode its
+/// journal. Dev-mode (fake) receipts only pass when the host itself runs in
+/// dev mode.
+pub fn verify_receipt(receipt: &Receipt, kind: ReceiptKind, mode: ProverMode) -> Result<VerifiedReceipt> {
+    let ctx = VerifierContext::default().with_dev_mode(mode.is_dev());
+    receipt
+        .verify_with_context(&ctx, kind.image_id())
+        .context("Receipt verification failed")?;
+
+    let bytes = &receipt.journal.bytes;
+    let mut verified = VerifiedReceipt {
+        end_pos:        None,
+        end_positions:  None,
+        plies:          None,
+        result:         None,
+        journal:        hex::encode(bytes),
+        journal_sha256: sha256_hex(bytes),
+    };
+    match kind {
+        ReceiptKind::Move => {
+            let (end_pos, _color): ([u8; 2], u8) = receipt.journal.decode().context("Unexpected journal")?;
+            verified.end_pos = Some(end_pos);
+        }
+        ReceiptKind::Batch => {
+            let batch = decode_batch_journal(bytes).map_err(|e| anyhow!("Unexpected batch journal: {e}"))?;
+            if batch.move_image_id != *Digest::from(METHOD_ID).as_bytes() {
+                bail!("Batch moves were proven with another move circuit");
+            }
+            verified.end_positions = Some(batch.end_positions().map(Square::pos).collect());
+        }
+        ReceiptKind::Replay => {
+            let replay = ReplayJournal::decode(bytes).map_err(|e| anyhow!("Unexpected replay journal: {e}"))?;
+            verified.plies = Some(replay.plies);
+            verified.result = Some(replay.result as u8);
+        }
+    }
+    Ok(verified)
+}
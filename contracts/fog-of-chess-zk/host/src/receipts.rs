@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+
+use crate::prover::{sha256_hex, ReceiptKind};
+
+/// On-disk store of full receipts, one JSON file per receipt named by the
+/// SHA256 of its contents. `MoveResponse` only carries what the contract
+/// needs; the receipt is what lets anyone re-check the proof off-chain later.
+/// Each file records which guest produced the receipt; files written before
+/// that was stored hold a bare receipt and load as `ReceiptKind::Move`.
+#[derive(Clone)]
+pub struct ReceiptStore {
+    dir: PathBuf,
+}
+
+impl ReceiptStore {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).with_context(|| format!("Creating {}", dir.display()))?;
+        Ok(ReceiptStore { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist `receipt` with its kind and return its id.
+    pub fn save(&self, kind: ReceiptKind, receipt: &Receipt) -> Result<String> {
+        let bytes = serde_json::to_vec(&StoredReceiptRef { kind, receipt })?;
+        let id = sha256_hex(&bytes);
+        let path = self.path_for(&id)?;
+        fs::write(&path, bytes).with_context(|| format!("Writing {}", path.display()))?;
+        Ok(id)
+    }
+
+    /// Load a receipt and its kind by id; `Ok(None)` when no such receipt
+    /// was stored.
+    pub fn load(&self, id: &str) -> Result<Option<(ReceiptKind, Receipt)>> {
+        let path = self.path_for(id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let bytes = fs::read(&path).with_context(|| format!("Reading {}", path.display()))?;
+        if let Ok(stored) = serde_json::from_slice::<StoredReceipt>(&bytes) {
+            return Ok(Some((stored.kind, stored.receipt)));
+        }
+        let receipt = serde_json::from_slice(&bytes).context("Corrupt receipt file")?;
+        Ok(Some((ReceiptKind::Move, receipt)))
+    }
+
+    // Ids come straight from HTTP bodies, so only accept what `save` produces.
+    fn path_for(&self, id: &str) -> Result<PathBuf> {
+        if id.len() != 64 || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid receipt id: expected 64 hex characters");
+        }
+        Ok(self.dir.join(format!("{}.json", id.to_ascii_lowercase())))
+    }
+}
+
+#[derive(Serialize)]
+struct StoredReceiptRef<'a> {
+    kind:    ReceiptKind,
+    receipt: &'a Receipt,
+}
+
+#[derive(Deserialize)]
+struct StoredReceipt {
+    kind:    ReceiptKind,
+    receipt: Receipt,
+}
@@ -0,0 +1,471 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::Result;
+use axum::body::{to_bytes, Body};
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::{routing::get, routing::post, Json, Router};
+use risc0_zkvm::sha::Digest;
+use risc0_zkvm::Receipt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::{info, warn};
+
+use methods::{BATCH_ID, METHOD_ID, REPLAY_ID};
+
+use crate::auth::{
+    Auth, ProveQueue, RateLimiter, ACCOUNT_HEADER, API_KEY_HEADER, SESSION_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use crate::cache::ProofCache;
+use crate::config::{ProverMode, VERIFIER_ID};
+use crate::metrics::{Metrics, Outcome};
+use crate::prover::{
+    self, BatchResponse, MoveRequest, MoveResponse, ProofStats, ReceiptKind, ReplayRequest, ReplayResponse,
+    ValidateResponse, MAX_BATCH_MOVES,
+};
+use crate::receipts::ReceiptStore;
+
+/// Origins the Vite dev server uses — the prover's usual neighbour.
+pub const DEFAULT_CORS_ORIGINS: &[&str] = &["http://localhost:5173", "http://127.0.0.1:5173"];
+
+// Same cap axum puts on Json bodies
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub mode:     ProverMode,
+    pub receipts: ReceiptStore,
+    pub cache:    Option<ProofCache>, // None with --no-cache
+    pub auth:     Arc<Auth>,
+    pub limiter:  RateLimiter,
+    pub queue:    ProveQueue,
+    pub metrics:  Metrics,
+}
+
+#[derive(Serialize)]
+struct StatusResponse {
+    mode:            ProverMode,
+    mode_label:      &'static str,
+    is_dev_mode:     bool,
+    image_id:        String,
+    verifier:        &'static str,
+    auth_required:   bool,
+    signed_requests: bool, // Stellar-signed requests accepted
+}
+
+pub enum CorsPolicy {
+    AnyOrigin,
+    Origins(Vec<String>),
+}
+
+#[derive(Deserialize)]
+struct BatchRequest {
+    moves: Vec<MoveRequest>,
+}
+
+/// Either a `receipt_id` returned by /prove, /prove-batch or /prove-replay,
+/// or a full receipt inline. Stored receipts carry their own kind; `kind`
+/// says which guest an inline receipt came from and defaults to a move.
+#[derive(Deserialize)]
+struct VerifyRequest {
+    receipt_id: Option<String>,
+    receipt:    Option<Receipt>,
+    #[serde(default)]
+    kind:       ReceiptKind,
+}
+
+#[derive(Serialize)]
+struct VerifyResponse {
+    valid:          bool,
+    error:          Option<String>,
+    kind:           ReceiptKind,
+    image_id:       String,
+    end_pos:        Option<[u8; 2]>,
+    end_positions:  Option<Vec<[u8; 2]>>,
+    plies:          Option<u32>,
+    result:         Option<u8>,
+    journal:        Option<String>,
+    journal_sha256: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+type ApiError = (StatusCode, Json<ErrorResponse>);
+
+fn internal_error(error: anyhow::Error) -> ApiError {
+    let error = format!("{error:#}");
+    warn!(%error, "Request failed");
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse { error }))
+}
+
+fn bad_request(error: String) -> ApiError {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }))
+}
+
+fn not_found(error: String) -> ApiError {
+    (StatusCode::NOT_FOUND, Json(ErrorResponse { error }))
+}
+
+fn rejected(status: StatusCode, error: String) -> ApiError {
+    (status, Json(ErrorResponse { error }))
+}
+
+/// Auth, per-client rate limit and queue depth for the endpoints that run
+/// the guest. The queue slot is held until the handler has answered.
+async fn admit(State(state): State<AppState>, request: Request, next: Next) -> Result<Response, ApiError> {
+    let peer = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|c| c.0.ip());
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| rejected(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large".into()))?;
+
+    let client = state
+        .auth
+        .authenticate(&parts.headers, &body, peer)
+        .map_err(|error| {
+            warn!(path = %parts.uri.path(), %error, "Rejected unauthenticated request");
+            rejected(StatusCode::UNAUTHORIZED, error)
+        })?;
+
+    if let Err(retry_after) = state.limiter.check(&client.rate_key()) {
+        warn!(%client, "Rate limit exceeded");
+        let error = format!("Rate limit exceeded — retry in {}s", retry_after.as_secs().max(1));
+        return Err(rejected(StatusCode::TOO_MANY_REQUESTS, error));
+    }
+
+    let Some(_slot) = state.queue.try_enter() else {
+        warn!(%client, max_queue = state.queue.max_depth(), "Prover queue full");
+        return Err(rejected(StatusCode::SERVICE_UNAVAILABLE, "Prover queue full — try again shortly".into()));
+    };
+
+    info!(%client, path = %parts.uri.path(), "Request admitted");
+    Ok(next.run(Request::from_parts(parts, Body::from(body))).await)
+}
+
+// serde's messages quote the offending value, which for a move request can be
+// the salt or start square — reply with the status only.
+fn move_request<T>(payload: Result<Json<T>, JsonRejection>) -> Result<T, ApiError> {
+    match payload {
+        Ok(Json(payload)) => Ok(payload),
+        Err(rejection) => {
+            let error = "Malformed move request".to_string();
+            Err((rejection.status(), Json(ErrorResponse { error })))
+        }
+    }
+}
+
+// A retry of a finished proof is answered from the cache. Cache trouble never
+// fails a request — worst case the proof is simply generated again.
+fn cached<T: DeserializeOwned>(state: &AppState, endpoint: &'static str, key: Option<&str>) -> Option<T> {
+    let hit = state.cache.as_ref()?.get(key?);
+    if hit.is_some() {
+        info!(key, "Serving cached proof");
+        state.metrics.record_outcome(endpoint, state.mode, Outcome::Cached);
+    }
+    hit
+}
+
+/// Unwrap a proving run, counting it on /metrics either way.
+fn proved<T>(
+    state: &AppState,
+    endpoint: &'static str,
+    result: Result<anyhow::Result<(T, Receipt, ProofStats)>, tokio::task::JoinError>,
+) -> Result<(T, Receipt), ApiError> {
+    match result.map_err(anyhow::Error::from).and_then(|r| r) {
+        Ok((response, receipt, stats)) => {
+            info!(endpoint, total_cycles = stats.total_cycles, segments = stats.segments, wall_time = ?stats.wall_time);
+            state.metrics.record(endpoint, state.mode, &stats);
+            Ok((response, receipt))
+        }
+        Err(error) => {
+            state.metrics.record_outcome(endpoint, state.mode, Outcome::Failed);
+            Err(internal_error(error))
+        }
+    }
+}
+
+fn cache_store<T: Serialize>(state: &AppState, key: Option<&str>, response: &T) {
+    if let (Some(cache), Some(key)) = (&state.cache, key) {
+        if let Err(error) = cache.put(key, response) {
+            warn!(key, error = %format!("{error:#}"), "Could not cache proof");
+        }
+    }
+}
+
+fn cache_key<T: Serialize>(state: &AppState, image_id: [u32; 8], input: &T) -> Option<String> {
+    state.cache.as_ref()?;
+    ProofCache::key(image_id, state.mode, input)
+        .inspect_err(|error| warn!(error = %format!("{error:#}"), "Could not derive cache key"))
+        .ok()
+}
+
+async fn status(State(state): State<AppState>) -> Json<StatusResponse> {
+    Json(StatusResponse {
+        mode:            state.mode,
+        mode_label:      state.mode.label(),
+        is_dev_mode:     state.mode.is_dev(),
+        image_id:        prover::image_id_hex(),
+        verifier:        VERIFIER_ID,
+        auth_required:   state.auth.is_required(),
+        signed_requests: state.auth.signed_requests(),
+    })
+}
+
+async fn validate_move(
+    State(state): State<AppState>,
+    payload: Result<Json<MoveRequest>, JsonRejection>,
+) -> Result<Json<ValidateResponse>, ApiError> {
+    let payload = move_request(payload)?;
+    prover::parse_commitment(&payload.commitment).map_err(bad_request)?;
+    if let Err(reason) = prover::precheck_move(&payload) {
+        state.metrics.record_outcome("validate", state.mode, Outcome::Rejected);
+        return Ok(Json(ValidateResponse::rejected(reason)));
+    }
+    info!(request = ?payload, "Validating move");
+
+    let started = Instant::now();
+    let result = tokio::task::spawn_blocking(move || prover::validate_move(&payload))
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|r| r);
+
+    match result {
+        Ok(response) if response.legal => {
+            let stats = ProofStats {
+                total_cycles: response.total_cycles.unwrap_or_default(),
+                user_cycles:  response.user_cycles.unwrap_or_default(),
+                segments:     response.segments.unwrap_or_default(),
+                wall_time:    started.elapsed(),
+            };
+            state.metrics.record("validate", state.mode, &stats);
+            Ok(Json(response))
+        }
+        Ok(response) => {
+            state.metrics.record_outcome("validate", state.mode, Outcome::Rejected);
+            Ok(Json(response))
+        }
+        Err(error) => {
+            state.metrics.record_outcome("validate", state.mode, Outcome::Failed);
+            Err(internal_error(error))
+        }
+    }
+}
+
+async fn prove_move(
+    State(state): State<AppState>,
+    payload: Result<Json<MoveRequest>, JsonRejection>,
+) -> Result<Json<MoveResponse>, ApiError> {
+    let payload = move_request(payload)?;
+    let input = prover::move_input(&payload).map_err(bad_request)?;
+    prover::precheck_move(&payload).map_err(bad_request)?;
+    let key = cache_key(&state, METHOD_ID, &input);
+    if let Some(hit) = cached(&state, "prove", key.as_deref()) {
+        return Ok(Json(hit));
+    }
+
+    info!(request = ?payload, "Proving move");
+    let mode = state.mode;
+    let result = tokio::task::spawn_blocking(move || prover::prove_move(&payload, mode)).await;
+    let (mut response, receipt) = proved(&state, "prove", result)?;
+    let receipt_id = state.receipts.save(ReceiptKind::Move, &receipt).map_err(internal_error)?;
+    info!(%receipt_id, is_dev_mode = response.is_dev_mode, "Proof ready");
+    response.receipt_id = Some(receipt_id);
+    cache_store(&state, key.as_deref(), &response);
+    Ok(Json(response))
+}
+
+async fn prove_batch(
+    State(state): State<AppState>,
+    payload: Result<Json<BatchRequest>, JsonRejection>,
+) -> Result<Json<BatchResponse>, ApiError> {
+    let BatchRequest { moves } = move_request(payload)?;
+    if moves.is_empty() || moves.len() > MAX_BATCH_MOVES {
+        return Err(bad_request(format!("A batch needs between 1 and {MAX_BATCH_MOVES} moves")));
+    }
+    if moves.iter().any(|m| m.color != moves[0].color) {
+        return Err(bad_request("A batch holds one side's moves, not both".into()));
+    }
+    let inputs = moves.iter().map(prover::move_input).collect::<Result<Vec<_>, _>>().map_err(bad_request)?;
+    for (i, request) in moves.iter().enumerate() {
+        prover::precheck_move(request).map_err(|e| bad_request(format!("Move {}: {e}", i + 1)))?;
+    }
+    let key = cache_key(&state, BATCH_ID, &inputs);
+    if let Some(hit) = cached(&state, "prove_batch", key.as_deref()) {
+        return Ok(Json(hit));
+    }
+    info!(moves = moves.len(), "Proving batch");
+
+    let mode = state.mode;
+    let result = tokio::task::spawn_blocking(move || prover::prove_batch(&moves, mode)).await;
+    let (mut response, receipt) = proved(&state, "prove_batch", result)?;
+    response.receipt_id = Some(state.receipts.save(ReceiptKind::Batch, &receipt).map_err(internal_error)?);
+    cache_store(&state, key.as_deref(), &response);
+    Ok(Json(response))
+}
+
+async fn prove_replay(
+    State(state): State<AppState>,
+    payload: Result<Json<ReplayRequest>, JsonRejection>,
+) -> Result<Json<ReplayResponse>, ApiError> {
+    let payload = move_request(payload)?;
+    prover::precheck_replay(&payload).map_err(bad_request)?;
+    let key = cache_key(&state, REPLAY_ID, &prover::replay_input(&payload));
+    if let Some(hit) = cached(&state, "prove_replay", key.as_deref()) {
+        return Ok(Json(hit));
+    }
+    info!(plies = payload.moves.len(), "Proving game replay");
+
+    let mode = state.mode;
+    let result = tokio::task::spawn_blocking(move || prover::prove_replay(&payload, mode)).await;
+    let (mut response, receipt) = proved(&state, "prove_replay", result)?;
+    response.receipt_id = Some(state.receipts.save(ReceiptKind::Replay, &receipt).map_err(internal_error)?);
+    cache_store(&state, key.as_deref(), &response);
+    Ok(Json(response))
+}
+
+async fn metrics(State(state): State<AppState>) -> ([(header::HeaderName, &'static str); 1], String) {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.render())
+}
+
+/// Verify a receipt against the image id of the guest that produced it
+/// locally — no network, no contract.
+async fn verify_receipt(
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyRequest>,
+) -> Result<Json<VerifyResponse>, ApiError> {
+    let (kind, receipt) = match (payload.receipt, payload.receipt_id) {
+        (Some(receipt), _) => (payload.kind, receipt),
+        (None, Some(id)) => state
+            .receipts
+            .load(&id)
+            .map_err(|e| bad_request(format!("{e:#}")))?
+            .ok_or_else(|| not_found(format!("No stored receipt with id {id}")))?,
+        (None, None) => return Err(bad_request("Expected `receipt_id` or `receipt`".into())),
+    };
+
+    let result = tokio::task::spawn_blocking(move || prover::verify_receipt(&receipt, kind, state.mode))
+        .await
+        .map_err(|e| internal_error(e.into()))?;
+
+    let image_id = Digest::from(kind.image_id()).to_string();
+    let response = match result {
+        Ok(verified) => VerifyResponse {
+            valid: true,
+            error: None,
+            kind,
+            image_id,
+            end_pos: verified.end_pos,
+            end_positions: verified.end_positions,
+            plies: verified.plies,
+            result: verified.result,
+            journal: Some(verified.journal),
+            journal_sha256: Some(verified.journal_sha256),
+        },
+        Err(e) => VerifyResponse {
+            valid: false,
+            error: Some(format!("{e:#}")),
+            kind,
+            image_id,
+            end_pos: None,
+            end_positions: None,
+            plies: None,
+            result: None,
+            journal: None,
+            journal_sha256: None,
+        },
+    };
+    Ok(Json(response))
+}
+
+fn cors_layer(cors: &CorsPolicy) -> Result<CorsLayer> {
+    let origins = match cors {
+        CorsPolicy::AnyOrigin => return Ok(CorsLayer::permissive()),
+        CorsPolicy::Origins(origins) => origins
+            .iter()
+            .map(|origin| HeaderValue::from_str(origin))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            HeaderName::from_static(API_KEY_HEADER),
+            HeaderName::from_static(ACCOUNT_HEADER),
+            HeaderName::from_static(SESSION_HEADER),
+            HeaderName::from_static(TIMESTAMP_HEADER),
+            HeaderName::from_static(SIGNATURE_HEADER),
+        ]))
+}
+
+pub fn router(state: AppState, cors: &CorsPolicy) -> Result<Router> {
+    // Everything that runs the guest or checks a receipt goes through `admit`
+    let guarded = Router::new()
+        .route("/prove", post(prove_move))
+        .route("/prove-batch", post(prove_batch))
+        .route("/prove-replay", post(prove_replay))
+        .route("/validate", post(validate_move))
+        .route("/verify", post(verify_receipt))
+        .route_layer(middleware::from_fn_with_state(state.clone(), admit));
+
+    Ok(Router::new()
+        .merge(guarded)
+        .route("/status", get(status))
+        .route("/metrics", get(metrics))
+        .layer(cors_layer(cors)?)
+        .with_state(state))
+}
+
+pub async fn serve(addr: SocketAddr, state: AppState, cors: &CorsPolicy) -> Result<()> {
+    info!("LANTERN CHESS — ZK PROVER SERVER");
+    info!(mode = state.mode.label(), image_id = %prover::image_id_hex(), verifier = VERIFIER_ID);
+    info!(receipts = %state.receipts.dir().display());
+    match &state.cache {
+        Some(cache) => info!(
+            proof_cache = %cache.dir().display(),
+            max_entries = cache.max_entries(),
+            max_age_secs = cache.max_age().map(|a| a.as_secs()),
+        ),
+        None => info!("Proof cache disabled"),
+    }
+    match cors {
+        CorsPolicy::AnyOrigin => warn!("CORS allows any origin"),
+        CorsPolicy::Origins(origins) => info!(cors = %origins.join(", ")),
+    }
+
+    info!(
+        auth_required = state.auth.is_required(),
+        signed_requests = state.auth.signed_requests(),
+        rate_limit_per_minute = state.limiter.per_minute(),
+        global_rate_limit_per_minute = state.limiter.global_per_minute(),
+        max_queue = state.queue.max_depth(),
+    );
+    if !state.auth.is_required() && !addr.ip().is_loopback() {
+        warn!("No authentication — anyone who can reach this port can spend proving time");
+    }
+
+    if state.mode.is_dev() {
+        info!("To enable real proofs: RISC0_DEV_MODE=0 cargo run --release (uses local Docker), \
+               or set BONSAI_API_KEY + BONSAI_API_URL for cloud proving");
+    }
+    if !addr.ip().is_loopback() {
+        warn!(%addr, "Listening beyond localhost — move requests carry hidden board data in cleartext");
+    }
+
+    let app = router(state, cors)?;
+
+    let listener = TcpListener::bind(addr).await?;
+    info!("Prover running on http://{addr} (POST /prove, /prove-batch, /prove-replay, /validate, /verify; GET /status, /metrics)");
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![allow(dead_code)] // each test binary uses a different subset
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use host::auth::{Auth, ProveQueue, RateLimiter};
+use host::cache::ProofCache;
+use host::config::ProverMode;
+use host::metrics::Metrics;
+use host::prover::{compute_commitment, MoveRequest, PieceColor};
+use host::receipts::ReceiptStore;
+use host::server::AppState;
+
+// Piece numbers the move guest expects
+pub const KNIGHT: u32 = 1;
+pub const ROOK:   u32 = 2;
+pub const BISHOP: u32 = 3;
+pub const PAWN:   u32 = 4;
+pub const QUEEN:  u32 = 5;
+pub const KING:   u32 = 6;
+
+pub const SALT: u32 = 0x5eed_1234;
+
+/// Guests are only embedded when the RISC Zero toolchain is installed; a
+/// `RISC0_SKIP_BUILD=1` build leaves the ELFs empty. Tests that need a guest
+/// are ignored unless the `guest-tests` feature is on, and then fail here
+/// rather than pass without running anything.
+pub fn require_guest() {
+    assert!(
+        !methods::METHOD_ELF.is_empty() && !methods::BATCH_ELF.is_empty() && !methods::REPLAY_ELF.is_empty(),
+        "guest-tests is on but the guests were not built (RISC0_SKIP_BUILD is set or the risc0 toolchain is missing)"
+    );
+}
+
+/// A move request whose commitment matches its start square.
+pub fn move_request(color: PieceColor, piece_type: u32, start_pos: [u8; 2], end_pos: [u8; 2]) -> MoveRequest {
+    MoveRequest {
+        start_pos,
+        end_pos,
+        piece_type,
+        color,
+        salt:       SALT,
+        commitment: hex::encode(compute_commitment(start_pos, piece_type as u8, color, SALT)),
+    }
+}
+
+/// Journal the move guest commits for `end_pos` played by `color`: risc0
+/// serde writes each byte as its own little-endian u32 word.
+pub fn expected_journal(end_pos: [u8; 2], color: PieceColor) -> Vec<u8> {
+    vec![end_pos[0], 0, 0, 0, end_pos[1], 0, 0, 0, color.code(), 0, 0, 0]
+}
+
+/// Dev-mode server state with everything stored under `dir` and no auth.
+pub fn app_state(dir: &Path) -> AppState {
+    AppState {
+        mode:     ProverMode::Dev,
+        receipts: ReceiptStore::open(dir.join("receipts")).unwrap(),
+        cache:    Some(ProofCache::open(dir.join("proof-cache"), 16, None).unwrap()),
+        auth:     Arc::new(Auth::new(HashSet::new(), HashSet::new())),
+        limiter:  RateLimiter::new(0),
+        queue:    ProveQueue::new(4),
+        metrics:  Metrics::default(),
+    }
+}
@@ -0,0 +1,204 @@
+//! Runs the guests in the executor and in dev mode — no Docker, no Bonsai.
+
+mod common;
+
+use common::*;
+use host::config::ProverMode;
+use host::prover::PieceColor::{Black, White};
+use host::prover::{self, sha256_hex, PieceColor, ReceiptKind, ReplayRequest};
+use fog_chess_core::{move_chain, Color, Square};
+use methods::METHOD_ID;
+use risc0_zkvm::sha::Digest;
+
+const LEGAL: &[(PieceColor, u32, [u8; 2], [u8; 2])] = &[
+    (White, KNIGHT, [7, 1], [5, 2]),
+    (White, KNIGHT, [4, 4], [3, 6]),
+    (White, ROOK,   [7, 0], [3, 0]),
+    (White, ROOK,   [4, 4], [4, 7]),
+    (White, BISHOP, [7, 2], [4, 5]),
+    (White, BISHOP, [3, 3], [0, 0]),
+    (White, PAWN,   [6, 4], [5, 4]), // white single step
+    (White, PAWN,   [6, 4], [4, 4]), // white double step from its start row
+    (Black, PAWN,   [1, 3], [3, 3]), // black double step from its start row
+    (White, PAWN,   [4, 4], [3, 5]), // diagonal capture
+    (Black, PAWN,   [1, 3], [2, 3]), // black single step
+    (Black, PAWN,   [3, 3], [4, 2]), // black diagonal capture
+    (White, QUEEN,  [7, 3], [3, 7]),
+    (White, QUEEN,  [7, 3], [2, 3]),
+    (White, KING,   [7, 4], [6, 4]),
+    (White, KING,   [7, 4], [6, 5]),
+];
+
+const ILLEGAL: &[(PieceColor, u32, [u8; 2], [u8; 2])] = &[
+    (White, KNIGHT, [7, 1], [5, 1]),
+    (White, KNIGHT, [4, 4], [4, 4]),
+    (White, ROOK,   [7, 0], [6, 1]),
+    (White, ROOK,   [4, 4], [4, 4]),
+    (White, BISHOP, [7, 2], [5, 2]),
+    (White, BISHOP, [3, 3], [3, 3]),
+    (White, PAWN,   [5, 4], [3, 4]), // double step away from the start rows
+    (White, PAWN,   [6, 4], [6, 5]), // sideways
+    (White, PAWN,   [6, 4], [3, 4]),
+    (White, PAWN,   [5, 4], [6, 4]), // backwards
+    (Black, PAWN,   [2, 3], [1, 3]), // backwards
+    (Black, PAWN,   [6, 4], [4, 4]), // backwards double step from white's start row
+    (White, QUEEN,  [7, 3], [5, 4]),
+    (White, QUEEN,  [7, 3], [7, 3]),
+    (White, KING,   [7, 4], [5, 4]),
+    (White, KING,   [7, 4], [7, 4]),
+    (White, 0,      [7, 4], [6, 4]), // unknown piece types
+    (White, 7,      [7, 4], [6, 4]),
+];
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn legal_moves_for_every_piece_are_accepted() {
+    require_guest();
+    for &(color, piece, start, end) in LEGAL {
+        let response = prover::validate_move(&move_request(color, piece, start, end)).unwrap();
+        assert!(response.legal, "{color:?} piece {piece} {start:?} -> {end:?}: {:?}", response.reason);
+        assert_eq!(response.end_pos, Some(end));
+
+        let journal = expected_journal(end, color);
+        assert_eq!(response.journal, Some(hex::encode(&journal)));
+        assert_eq!(response.journal_sha256, Some(sha256_hex(&journal)));
+        assert!(response.user_cycles.unwrap() > 0);
+        assert!(response.total_cycles.unwrap() >= response.user_cycles.unwrap());
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn illegal_moves_for_every_piece_are_rejected() {
+    require_guest();
+    for &(color, piece, start, end) in ILLEGAL {
+        let response = prover::validate_move(&move_request(color, piece, start, end)).unwrap();
+        assert!(!response.legal, "{color:?} piece {piece} {start:?} -> {end:?} should be illegal");
+        assert!(response.reason.unwrap().contains("Illegal move"));
+        assert!(response.journal.is_none());
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn commitment_for_another_square_is_rejected() {
+    require_guest();
+    let mut request = move_request(White, ROOK, [7, 0], [3, 0]);
+    request.commitment = hex::encode(prover::compute_commitment([7, 7], ROOK as u8, White, SALT));
+
+    let response = prover::validate_move(&request).unwrap();
+    assert!(!response.legal);
+    assert!(response.reason.unwrap().contains("Commitment verification failed"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn commitment_for_another_piece_or_color_is_rejected() {
+    require_guest();
+    // A rook's commitment can't be used to move it like a queen
+    let mut request = move_request(White, QUEEN, [7, 0], [5, 2]);
+    request.commitment = hex::encode(prover::compute_commitment([7, 0], ROOK as u8, White, SALT));
+    let response = prover::validate_move(&request).unwrap();
+    assert!(response.reason.unwrap().contains("Commitment verification failed"));
+
+    // Nor can a white pawn's commitment walk it backwards as a black pawn
+    let mut request = move_request(Black, PAWN, [4, 4], [5, 4]);
+    request.commitment = hex::encode(prover::compute_commitment([4, 4], PAWN as u8, White, SALT));
+    let response = prover::validate_move(&request).unwrap();
+    assert!(response.reason.unwrap().contains("Commitment verification failed"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn dev_mode_proof_verifies_and_carries_a_dev_seal() {
+    require_guest();
+    let (response, receipt, stats) = prover::prove_move(&move_request(White, KNIGHT, [7, 1], [5, 2]), ProverMode::Dev).unwrap();
+
+    let journal = expected_journal([5, 2], White);
+    assert!(response.is_dev_mode);
+    assert_eq!(response.journal, hex::encode(&journal));
+    assert_eq!(response.seal, format!("44455621{}", sha256_hex(&journal)));
+    assert_eq!(response.image_id, prover::image_id_hex());
+    assert!(stats.segments > 0);
+
+    let verified = prover::verify_receipt(&receipt, ReceiptKind::Move, ProverMode::Dev).unwrap();
+    assert_eq!(verified.end_pos, Some([5, 2]));
+    assert_eq!(verified.journal_sha256, response.journal_sha256);
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn batch_journal_lists_every_end_pos_after_the_move_image_id() {
+    require_guest();
+    let moves = [
+        move_request(White, PAWN, [6, 4], [4, 4]),
+        move_request(White, KNIGHT, [7, 6], [5, 5]),
+        move_request(White, BISHOP, [7, 5], [4, 2]),
+    ];
+    let (response, receipt, _) = prover::prove_batch(&moves, ProverMode::Dev).unwrap();
+
+    let mut journal = Digest::from(METHOD_ID).as_bytes().to_vec();
+    journal.extend_from_slice(&[0, 4, 4, 5, 5, 4, 2]);
+    assert_eq!(response.journal, hex::encode(&journal));
+    assert_eq!(response.moves, 3);
+    assert_eq!(response.move_image_id, prover::image_id_hex());
+
+    let verified = prover::verify_receipt(&receipt, ReceiptKind::Batch, ProverMode::Dev).unwrap();
+    assert_eq!(verified.end_positions, Some(vec![[4, 4], [5, 5], [4, 2]]));
+    assert!(prover::verify_receipt(&receipt, ReceiptKind::Move, ProverMode::Dev).is_err());
+}
+
+fn board(pieces: &[(u8, [u8; 2])]) -> Vec<u8> {
+    let mut board = vec![0; 64];
+    for &(piece, [row, col]) in pieces {
+        board[row as usize * 8 + col as usize] = piece;
+    }
+    board
+}
+
+fn board_commitment(board: &[u8], salt: u32) -> String {
+    let mut preimage = board.to_vec();
+    preimage.extend_from_slice(&salt.to_be_bytes());
+    sha256_hex(&preimage)
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn replay_commits_both_boards_and_the_winner() {
+    require_guest();
+    // White's rook runs up the open a-file and takes the black king on move 2
+    let request = ReplayRequest {
+        white_board: board(&[(KING as u8, [7, 4]), (ROOK as u8, [7, 0])]),
+        white_salt:  11,
+        black_board: board(&[(KING as u8, [0, 0]), (PAWN as u8, [1, 7])]),
+        black_salt:  22,
+        moves:       vec![([7, 4], [6, 4]), ([1, 7], [2, 7]), ([7, 0], [0, 0])],
+    };
+    let (response, receipt, _) = prover::prove_replay(&request, ProverMode::Dev).unwrap();
+
+    assert_eq!(response.white_commitment, board_commitment(&request.white_board, 11));
+    assert_eq!(response.black_commitment, board_commitment(&request.black_board, 22));
+    assert_eq!(response.plies, 3);
+    assert_eq!(response.result, 1);
+    // The chains the contract builds from the moves it verified during play
+    let ends = |squares: &[[u8; 2]]| squares.iter().map(|&pos| Square::from_pos(pos).unwrap()).collect::<Vec<_>>();
+    assert_eq!(response.white_moves, hex::encode(move_chain(ends(&[[6, 4], [0, 0]]), Color::White)));
+    assert_eq!(response.black_moves, hex::encode(move_chain(ends(&[[2, 7]]), Color::Black)));
+
+    let verified = prover::verify_receipt(&receipt, ReceiptKind::Replay, ProverMode::Dev).unwrap();
+    assert_eq!((verified.plies, verified.result), (Some(3), Some(1)));
+}
+
+#[test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+fn replay_rejects_a_move_through_a_piece() {
+    require_guest();
+    let request = ReplayRequest {
+        white_board: board(&[(KING as u8, [7, 4]), (ROOK as u8, [7, 0]), (PAWN as u8, [6, 0])]),
+        white_salt:  11,
+        black_board: board(&[(KING as u8, [0, 0])]),
+        black_salt:  22,
+        moves:       vec![([7, 0], [0, 0])],
+    };
+    assert!(prover::prove_replay(&request, ProverMode::Dev).is_err());
+}
@@ -0,0 +1,393 @@
+//! Exercises the axum router in-process. Tests that need a proof run with
+//! `--features guest-tests`; the rest run anywhere.
+
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use common::*;
+use ed25519_dalek::{Signer, SigningKey};
+use fog_chess_core::{Board, Color};
+use host::auth::{
+    signed_message, Auth, RateLimiter, ACCOUNT_HEADER, SESSION_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use host::prover::PieceColor;
+use host::server::{router, AppState, CorsPolicy};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tower::ServiceExt;
+
+fn app(state: AppState) -> Router {
+    router(state, &CorsPolicy::AnyOrigin).unwrap()
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, Vec<u8>) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes().to_vec();
+    (status, body)
+}
+
+fn post(path: &str, body: &Value) -> Request<Body> {
+    Request::post(path)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get(path: &str) -> Request<Body> {
+    Request::get(path).body(Body::empty()).unwrap()
+}
+
+fn move_json(piece_type: u32, start_pos: [u8; 2], end_pos: [u8; 2]) -> Value {
+    let request = move_request(PieceColor::White, piece_type, start_pos, end_pos);
+    json!({
+        "start_pos":  request.start_pos,
+        "end_pos":    request.end_pos,
+        "piece_type": request.piece_type,
+        "color":      request.color,
+        "salt":       request.salt,
+        "commitment": request.commitment,
+    })
+}
+
+#[tokio::test]
+async fn status_reports_dev_mode_and_open_access() {
+    let dir = tempfile::tempdir().unwrap();
+    let (status, body) = send(&app(app_state(dir.path())), get("/status")).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["mode"], "dev");
+    assert_eq!(body["is_dev_mode"], true);
+    assert_eq!(body["auth_required"], false);
+}
+
+#[tokio::test]
+async fn malformed_move_request_does_not_echo_private_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut request = move_json(ROOK, [7, 0], [3, 0]);
+    request["salt"] = json!("my-secret-salt");
+
+    let (status, body) = send(&app(app_state(dir.path())), post("/prove", &request)).await;
+
+    assert!(status.is_client_error());
+    let body = String::from_utf8(body).unwrap();
+    assert!(body.contains("Malformed move request"));
+    assert!(!body.contains("my-secret-salt"));
+}
+
+#[tokio::test]
+async fn bad_commitment_is_a_bad_request() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut request = move_json(ROOK, [7, 0], [3, 0]);
+    request["commitment"] = json!("abcd");
+
+    let (status, body) = send(&app(app_state(dir.path())), post("/validate", &request)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("32 bytes"));
+}
+
+#[tokio::test]
+async fn batch_size_is_bounded() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+
+    let (status, _) = send(&app, post("/prove-batch", &json!({ "moves": [] }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let moves: Vec<Value> = (0..33).map(|_| move_json(KING, [7, 4], [6, 4])).collect();
+    let (status, _) = send(&app, post("/prove-batch", &json!({ "moves": moves }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let mut black = move_json(PAWN, [1, 3], [2, 3]);
+    black["color"] = json!("black");
+    let (status, body) = send(&app, post("/prove-batch", &json!({ "moves": [move_json(KING, [7, 4], [6, 4]), black] }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("one side's moves"));
+}
+
+#[tokio::test]
+async fn illegal_moves_are_turned_away_before_the_guest_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+    let illegal = move_json(KNIGHT, [7, 1], [5, 1]);
+
+    let (status, body) = send(&app, post("/prove", &illegal)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("Illegal move"));
+
+    let (status, body) = send(&app, post("/validate", &illegal)).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["legal"], false);
+
+    let mut wrong_square = move_json(ROOK, [7, 0], [3, 0]);
+    wrong_square["start_pos"] = json!([7, 7]);
+    let moves = json!({ "moves": [move_json(KING, [7, 4], [6, 4]), wrong_square] });
+    let (status, body) = send(&app, post("/prove-batch", &moves)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("Move 2: Commitment verification failed"));
+}
+
+#[tokio::test]
+async fn illegal_replay_is_a_bad_request() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+    let board = Board::initial();
+    let replay = |moves: Value| {
+        json!({
+            "white_board": board.side(Color::White).to_vec(),
+            "white_salt":  SALT,
+            "black_board": board.side(Color::Black).to_vec(),
+            "black_salt":  SALT,
+            "moves":       moves,
+        })
+    };
+
+    // Third ply: a white pawn tries to leap three squares
+    let moves = json!([[[6, 4], [4, 4]], [[1, 3], [3, 3]], [[6, 0], [3, 0]]]);
+    let (status, body) = send(&app, post("/prove-replay", &replay(moves))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("Illegal move 3"));
+
+    let mut kingless = replay(json!([]));
+    kingless["white_board"] = json!(vec![0u8; 64]);
+    let (status, body) = send(&app, post("/prove-replay", &kingless)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body).unwrap().contains("Invalid board"));
+}
+
+#[tokio::test]
+async fn verify_rejects_unknown_and_invalid_receipt_ids() {
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+
+    let (status, _) = send(&app, post("/verify", &json!({ "receipt_id": "0".repeat(64) }))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send(&app, post("/verify", &json!({ "receipt_id": "../../etc/passwd" }))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, post("/verify", &json!({}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn api_key_is_required_when_configured() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = app_state(dir.path());
+    state.auth = Arc::new(Auth::new(HashSet::from(["team-key".to_string()]), HashSet::new()));
+    let app = app(state);
+
+    // Commitment is deliberately invalid: an admitted request then stops at a 400
+    let mut body = move_json(ROOK, [7, 0], [3, 0]);
+    body["commitment"] = json!("00");
+
+    let (status, _) = send(&app, post("/validate", &body)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let mut request = post("/validate", &body);
+    request.headers_mut().insert("x-api-key", "wrong".parse().unwrap());
+    let (status, _) = send(&app, request).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let mut request = post("/validate", &body);
+    request.headers_mut().insert("x-api-key", "team-key".parse().unwrap());
+    let (status, _) = send(&app, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Receipt checks are guarded too; status stays open
+    let (status, _) = send(&app, post("/verify", &json!({}))).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, get("/status")).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+fn account(key: &SigningKey) -> String {
+    stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string().as_str().to_owned()
+}
+
+/// A request to `path` signed the way Freighter's signMessage does it.
+fn signed_post(key: &SigningKey, path: &str, body: &Value) -> Request<Body> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let body = body.to_string();
+    let mut hasher = Sha256::new();
+    hasher.update(b"Stellar Signed Message:\n");
+    hasher.update(signed_message(7, timestamp, body.as_bytes()).as_bytes());
+    let signature = BASE64_STANDARD.encode(key.sign(&hasher.finalize()).to_bytes());
+
+    Request::post(path)
+        .header("content-type", "application/json")
+        .header(ACCOUNT_HEADER, account(key))
+        .header(SESSION_HEADER, "7")
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(SIGNATURE_HEADER, signature)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn signed_requests_need_an_allowed_account() {
+    let dir = tempfile::tempdir().unwrap();
+    let player = SigningKey::from_bytes(&[1; 32]);
+    let stranger = SigningKey::from_bytes(&[2; 32]);
+    let mut state = app_state(dir.path());
+    state.auth = Arc::new(Auth::new(HashSet::new(), HashSet::from([account(&player)])));
+    let app = app(state);
+
+    let mut body = move_json(ROOK, [7, 0], [3, 0]);
+    body["commitment"] = json!("00");
+
+    let (status, _) = send(&app, signed_post(&player, "/validate", &body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A valid signature from a fresh key is not enough
+    let (status, error) = send(&app, signed_post(&stranger, "/validate", &body)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(String::from_utf8(error).unwrap().contains("not allowed"));
+}
+
+#[tokio::test]
+async fn rate_limit_applies_per_client() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = app_state(dir.path());
+    state.limiter = RateLimiter::new(2);
+    let app = app(state);
+
+    let mut body = move_json(ROOK, [7, 0], [3, 0]);
+    body["commitment"] = json!("00");
+
+    for _ in 0..2 {
+        let (status, _) = send(&app, post("/validate", &body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (status, _) = send(&app, post("/validate", &body)).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn global_rate_limit_applies_across_clients() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = app_state(dir.path());
+    state.auth = Arc::new(Auth::new(HashSet::from(["a".to_string(), "b".to_string()]), HashSet::new()));
+    state.limiter = RateLimiter::new(10).with_global_limit(3);
+    let app = app(state);
+
+    let mut body = move_json(ROOK, [7, 0], [3, 0]);
+    body["commitment"] = json!("00");
+    let keyed = |key: &str| {
+        let mut request = post("/validate", &body);
+        request.headers_mut().insert("x-api-key", key.parse().unwrap());
+        request
+    };
+
+    for key in ["a", "b", "a"] {
+        let (status, _) = send(&app, keyed(key)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (status, _) = send(&app, keyed("b")).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[test]
+fn rate_limiter_tracks_a_bounded_number_of_clients() {
+    let limiter = RateLimiter::new(5).with_max_clients(2);
+    assert!(limiter.check("a").is_ok());
+    assert!(limiter.check("b").is_ok());
+
+    // A third client waits for a window to finish; known clients still count
+    assert!(limiter.check("c").is_err());
+    assert!(limiter.check("a").is_ok());
+}
+
+#[tokio::test]
+async fn metrics_are_prometheus_text() {
+    let dir = tempfile::tempdir().unwrap();
+    let (status, body) = send(&app(app_state(dir.path())), get("/metrics")).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let body = String::from_utf8(body).unwrap();
+    assert!(body.contains("# TYPE prover_requests_total counter"));
+    assert!(body.contains("# TYPE prover_wall_time_seconds histogram"));
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+async fn validate_reports_legality() {
+    require_guest();
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+
+    let (status, body) = send(&app, post("/validate", &move_json(KNIGHT, [7, 1], [5, 2]))).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["legal"], true);
+    assert_eq!(body["end_pos"], json!([5, 2]));
+
+    let (status, body) = send(&app, post("/validate", &move_json(KNIGHT, [7, 1], [5, 1]))).await;
+    assert_eq!(status, StatusCode::OK);
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["legal"], false);
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+async fn prove_stores_a_verifiable_receipt_and_caches_retries() {
+    require_guest();
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+    let request = move_json(QUEEN, [7, 3], [3, 7]);
+
+    let (status, first) = send(&app, post("/prove", &request)).await;
+    assert_eq!(status, StatusCode::OK);
+    let first: Value = serde_json::from_slice(&first).unwrap();
+    assert_eq!(first["is_dev_mode"], true);
+    assert_eq!(first["journal"], hex::encode(expected_journal([3, 7], PieceColor::White)));
+
+    // Retry is served from the cache — same receipt, no second proving run
+    let (_, second) = send(&app, post("/prove", &request)).await;
+    let second: Value = serde_json::from_slice(&second).unwrap();
+    assert_eq!(first, second);
+
+    let (_, metrics) = send(&app, get("/metrics")).await;
+    let metrics = String::from_utf8(metrics).unwrap();
+    assert!(metrics.contains(r#"prover_requests_total{endpoint="prove",mode="dev",outcome="ok"} 1"#));
+    assert!(metrics.contains(r#"prover_requests_total{endpoint="prove",mode="dev",outcome="cached"} 1"#));
+
+    let (status, verified) = send(&app, post("/verify", &json!({ "receipt_id": first["receipt_id"] }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let verified: Value = serde_json::from_slice(&verified).unwrap();
+    assert_eq!(verified["valid"], true);
+    assert_eq!(verified["end_pos"], json!([3, 7]));
+}
+
+#[tokio::test]
+#[cfg_attr(not(feature = "guest-tests"), ignore = "needs the RISC Zero guest; run with --features guest-tests")]
+async fn batch_receipt_verifies_against_the_batch_image_id() {
+    require_guest();
+    let dir = tempfile::tempdir().unwrap();
+    let app = app(app_state(dir.path()));
+    let moves = json!({ "moves": [move_json(PAWN, [6, 4], [4, 4]), move_json(KNIGHT, [7, 6], [5, 5])] });
+
+    let (status, proved) = send(&app, post("/prove-batch", &moves)).await;
+    assert_eq!(status, StatusCode::OK);
+    let proved: Value = serde_json::from_slice(&proved).unwrap();
+
+    let (status, verified) = send(&app, post("/verify", &json!({ "receipt_id": proved["receipt_id"] }))).await;
+    assert_eq!(status, StatusCode::OK);
+    let verified: Value = serde_json::from_slice(&verified).unwrap();
+    assert_eq!(verified["valid"], true);
+    assert_eq!(verified["kind"], "batch");
+    assert_eq!(verified["image_id"], proved["image_id"]);
+    assert_eq!(verified["journal"], proved["journal"]);
+    assert_eq!(verified["end_positions"], json!([[4, 4], [5, 5]]));
+}
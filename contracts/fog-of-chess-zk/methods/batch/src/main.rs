@@ -0,0 +1,41 @@
+#![no_main]
+use fog_chess_core::journal::decode_move_journal;
+use risc0_zkvm::guest::env;
+use risc0_zkvm::sha::Digest;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    // 1. Read inputs from the Host
+    // We expect: move circuit image id (METHOD_ID), then each move's journal in play order.
+    // Every journal must be backed by a move receipt the host added as an assumption.
+    let (move_image_id, journals): ([u32; 8], Vec<Vec<u8>>) = env::read();
+    assert!(!journals.is_empty(), "Batch has no moves!");
+
+    let move_image_id = Digest::from(move_image_id);
+
+    // 2. Journal layout the contract parses: move image id (32 bytes) ‖ color (1 byte) ‖ end_pos (2 bytes) per move
+    let mut batch_journal = Vec::with_capacity(33 + 2 * journals.len());
+    batch_journal.extend_from_slice(move_image_id.as_bytes());
+    let mut batch_color = None;
+
+    for journal in &journals {
+        // Composition: this only resolves if a receipt for (move_image_id, journal) exists,
+        // so every ply in the batch went through the single-move legality check.
+        env::verify(move_image_id, journal).unwrap();
+
+        let (end_pos, color) = decode_move_journal(journal).expect("Malformed move journal!");
+        // One batch covers one player's moves, all on the same side
+        match batch_color {
+            None => {
+                batch_color = Some(color);
+                batch_journal.push(color.code());
+            }
+            Some(side) => assert_eq!(side, color, "Batch mixes white and black moves!"),
+        }
+        batch_journal.extend_from_slice(&end_pos.pos());
+    }
+
+    // 3. Commit the result
+    env::commit_slice(&batch_journal);
+}
@@ -0,0 +1,71 @@
+#![no_main]
+use fog_chess_core::journal::{GameResult, ReplayJournal};
+use fog_chess_core::{board_commitment, is_legal_move, move_chain, Board, Color, Piece, Square};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+/// (from, to) — [row, col] squares
+type Move = ([u8; 2], [u8; 2]);
+
+fn square(pos: [u8; 2]) -> Square {
+    Square::from_pos(pos).expect("Square off the board!")
+}
+
+pub fn main() {
+    // 1. Read inputs from the Host
+    // We expect: each player's revealed board and board salt (white = player1 first),
+    // then every move of the game in play order as (from, to). White moves first.
+    let (white_board, white_salt, black_board, black_salt, moves):
+        (Vec<u8>, u32, Vec<u8>, u32, Vec<Move>) = env::read();
+
+    // 64 squares of known pieces, one king each, no shared squares
+    let mut board = Board::decode(&white_board, &black_board).expect("Malformed board!");
+
+    // 2. Recompute both commitments — SHA256(board ‖ salt), which the contract checks
+    //    against what each player stored with `commit_board`
+    let white_commitment = board_commitment(&white_board, white_salt);
+    let black_commitment = board_commitment(&black_board, black_salt);
+
+    // 3. Replay the whole game under the fog rules in fog-chess-core, keeping
+    //    each side's end squares for the move chains below
+    let mut result = GameResult::Undecided;
+    let mut side = Color::White;
+    let mut white_ends = Vec::new();
+    let mut black_ends = Vec::new();
+    for &(from, to) in &moves {
+        assert_eq!(result, GameResult::Undecided, "Move played after the game ended!");
+        let (from, to) = (square(from), square(to));
+        assert!(is_legal_move(&board, side, from, to), "Illegal move in replay!");
+        match side {
+            Color::White => white_ends.push(to),
+            Color::Black => black_ends.push(to),
+        }
+
+        if let Some((_, Piece::King)) = board.apply(from, to) {
+            result = match side {
+                Color::White => GameResult::WhiteWins,
+                Color::Black => GameResult::BlackWins,
+            };
+        }
+        side = side.opposite();
+    }
+
+    // 4. Chain each side's move journals the way the contract did as it verified
+    //    them, so the replay can only be of the game actually played on-chain
+    let white_moves = move_chain(white_ends, Color::White);
+    let black_moves = move_chain(black_ends, Color::Black);
+
+    // 5. Commit the result
+    // Journal layout the contract parses: white commitment (32) ‖ black commitment (32)
+    // ‖ white move chain (32) ‖ black move chain (32) ‖ plies (u32 big-endian) ‖ result (1)
+    let journal = ReplayJournal {
+        white_commitment,
+        black_commitment,
+        white_moves,
+        black_moves,
+        plies: moves.len() as u32,
+        result,
+    };
+    env::commit_slice(&journal.encode());
+}
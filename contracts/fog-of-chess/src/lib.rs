@@ -1,25 +1,72 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror,
-    Env, BytesN, Address, Vec, Bytes,
+    contract, contractimpl, contracttype, contracterror, vec,
+    Env, BytesN, Address, Vec, Bytes, IntoVal,
 };
 
 // ── Nethermind RISC Zero Groth16 Verifier (already deployed on testnet) ──────
 // https://github.com/NethermindEth/stellar-risc0-verifier
 const NETHERMIND_VERIFIER_ID: &str = "CBY3GOBGQXDGRR4K2KYJO2UOXDW5NRW6UKIQHUBNBNU2V3BXQBXGTVX7";
 
-mod risc0_verifier {
-    use soroban_sdk::contractimport;
-    // Import the Nethermind verifier interface
-    // verify(journal: BytesN<32>, image_id: BytesN<32>, seal: Bytes) -> Result<(), VerifierError>
-    contractimport!(file = "../../target/wasm32v1-none/release/groth16_verifier.wasm");
-}
+// Seal layouts: selector(4) + proof. A Groth16 proof is always 256 bytes.
+const GROTH16_SELECTOR: [u8; 4] = [0x73, 0xc4, 0x57, 0xba];
+const GROTH16_SEAL_LEN: u32     = 4 + 256;
+// Dev-mode prover output: b"DEV!" + sha256(journal). Only accepted when the
+// admin has switched the dev verifier on — never on a production deployment.
+const DEV_SELECTOR: [u8; 4]     = *b"DEV!";
+const DEV_SEAL_LEN: u32         = 4 + 32;
+
+// Batch journal: move image id(32) ‖ color(1) ‖ end_pos(2) per move. Must
+// match MAX_BATCH_MOVES in the prover host.
+const MAX_BATCH_MOVES: u32 = 32;
+const BATCH_HEADER_LEN: u32 = 32 + 1;
+
+// Points each player stakes at the hub for a game
+const STAKE_POINTS: i128 = 1000;
+
+// Color codes the move circuit commits: player1 plays white, player2 black
+const WHITE: u32 = 0;
+const BLACK: u32 = 1;
+
+// Move journal: risc0 serde of (end_pos, color), each a u32 LE word
+const MOVE_JOURNAL_LEN: u32 = 3 * 4;
+
+// Replay journal: white commitment(32) ‖ black commitment(32) ‖ white move
+// chain(32) ‖ black move chain(32) ‖ plies(u32 BE) ‖ result(1)
+const REPLAY_JOURNAL_LEN: u32 = 32 * 4 + 4 + 1;
+const REPLAY_WHITE_WINS: u8   = 1;
+const REPLAY_BLACK_WINS: u8   = 2;
+
+// extend_deadline limits: one call adds at most DEFAULT_MAX_EXTENSION ledgers
+// (about a day at 5 s a ledger) unless the admin sets another cap, and each
+// player gets MAX_EXTENSIONS_PER_PLAYER calls per session
+const DEFAULT_MAX_EXTENSION: u32     = 17_280;
+const MAX_EXTENSIONS_PER_PLAYER: u32 = 3;
+
+// Ledgers the other player has to dispute a result claimed with end_game
+// (about a day at 5 s a ledger)
+const CHALLENGE_LEDGERS: u32 = 17_280;
 
-mod game_hub {
-    use soroban_sdk::contractimport;
-    contractimport!(file = "../../target/wasm32v1-none/release/mock_game_hub.wasm");
+// Revealed boards: one byte per square (row * 8 + col), 0 = empty, else the
+// circuit's piece number (1 knight … 6 king)
+const BOARD_SQUARES: u32 = 64;
+const MAX_PIECE: u8      = 6;
+
+pub mod risc0_verifier {
+    use soroban_sdk::{contractclient, Bytes, BytesN, Env};
+
+    // The Nethermind verifier interface. The deployed contract returns
+    // Result<(), VerifierError>; any error reverts the calling transaction,
+    // so the client only needs the success case.
+    #[contractclient(name = "Client")]
+    pub trait Verifier {
+        fn verify(env: Env, journal: BytesN<32>, image_id: BytesN<32>, seal: Bytes);
+    }
 }
 
+// Game Hub interface shared by every game
+use game_hub_client::{GameHubClient, GameHubReaderClient, Outcome, SessionStatus};
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -28,6 +75,18 @@ pub enum DataKey {
     Admin,
     GameHub,
     ImageId,   // Stores the METHOD_ID for your ZK circuit
+    DevVerifier, // bool — accept dev-mode seals (local testing only)
+    BatchImageId, // BATCH_ID of the move-aggregation circuit
+    Plies(u32, Address), // u32 — moves verified for this player in the session so far
+    ReplayImageId, // REPLAY_ID of the whole-game replay circuit
+    Finalized(u32), // bool — result settled by a replay proof, can't change again
+    Audit(u32),     // AuditRecord — boards revealed after the game, and disputes
+    Verifier,       // Address — Groth16 verifier, NETHERMIND_VERIFIER_ID unless set
+    Color(u32, Address), // u32 — side the player moves in the session, WHITE or BLACK
+    MoveChain(u32, Address), // BytesN<32> — SHA256(chain ‖ sha256(move journal)) over the session's verified moves
+    MaxExtension,   // u32 — most ledgers one extend_deadline may add, DEFAULT_MAX_EXTENSION unless set
+    Extensions(u32, Address), // u32 — extend_deadline calls this player made in the session
+    Claim(u32),     // ResultClaim — result claimed with end_game, open to challenge
 }
 
 #[contracterror]
@@ -43,17 +102,80 @@ pub enum Error {
     SessionNotFound     = 7,
     NotAuthorized       = 8,
     VerificationFailed  = 9,
+    DevProofRejected    = 10,
+    AlreadyFinalized    = 11,
+    GameUndecided       = 12,
+    GameNotOver         = 13,
+    InvalidBoard        = 14,
+    CommitmentMismatch  = 15,
+    AlreadyRevealed     = 16,
+    NotRevealed         = 17,
+    AlreadyFlagged      = 18,
+    GameOver            = 19,
+    WrongColor          = 20,
+    ReplayMismatch      = 21,
+    DeadlineOverflow    = 22,
+    ExtensionTooLong    = 23,
+    NoExtensionsLeft    = 24,
+    AlreadyClaimed      = 25,
+    NoClaim             = 26,
+    ChallengeOpen       = 27,
+    ChallengeClosed     = 28,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct ZKProof {
-    pub seal:          Bytes,       // selector(4) + groth16 proof bytes
-    pub journal_sha256:BytesN<32>,  // SHA256 of journal (end_pos)
+    pub seal:          Bytes,       // selector(4) + groth16 proof bytes (or dev seal)
+    pub journal:       Bytes,       // move journal: end row, end col, color
     pub image_id:      BytesN<32>,  // METHOD_ID — identifies the circuit
     pub public_inputs: Vec<BytesN<32>>, // [0] = board commitment
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct BatchProof {
+    pub seal:    Bytes, // selector(4) + groth16 proof over the batch journal (or dev seal)
+    pub journal: Bytes, // move image id(32) ‖ color(1) ‖ end_pos(2) per move, in play order
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ReplayProof {
+    pub seal:    Bytes, // selector(4) + groth16 proof over the replay journal (or dev seal)
+    pub journal: Bytes, // white commitment(32) ‖ black commitment(32) ‖ plies(4) ‖ result(1)
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MismatchFlag {
+    pub flagged_by: Address,
+    pub square:     u32, // row * 8 + col where the revealed board disagrees with play
+    pub ledger:     u32, // ledger sequence the flag was raised in
+}
+
+/// Public end-of-game record: each player's revealed setup plus any
+/// disputes the opponent raised against it.
+#[contracttype]
+#[derive(Clone)]
+pub struct AuditRecord {
+    pub player1_board: Option<Bytes>,
+    pub player2_board: Option<Bytes>,
+    pub flags:         Vec<MismatchFlag>,
+}
+
+/// A result one player reported with `end_game`. The hub only hears of it
+/// once the other player agrees, a replay proof settles the game, or the
+/// window closes unchallenged.
+#[contracttype]
+#[derive(Clone)]
+pub struct ResultClaim {
+    pub claimed_by:  Address,
+    pub player1_won: bool,
+    pub deadline:    u32,  // last ledger the other player can challenge in
+    pub challenged:  bool,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct GameSession {
@@ -67,6 +189,138 @@ pub struct GameSession {
 #[contract]
 pub struct FogOfChessContract;
 
+/// Route a seal by its selector and verify it against `journal_sha256` and
+/// `image_id`. Dev seals never reach the real verifier.
+fn check_seal(
+    env:            &Env,
+    seal:           &Bytes,
+    journal_sha256: &BytesN<32>,
+    image_id:       &BytesN<32>,
+) -> Result<(), Error> {
+    if seal.len() < 4 {
+        return Err(Error::InvalidProofFormat);
+    }
+    let mut selector = [0u8; 4];
+    seal.slice(0..4).copy_into_slice(&mut selector);
+
+    if selector == DEV_SELECTOR {
+        return verify_dev_seal(env, seal, journal_sha256);
+    }
+    if selector != GROTH16_SELECTOR || seal.len() != GROTH16_SEAL_LEN {
+        return Err(Error::InvalidProofFormat);
+    }
+
+    let verifier = risc0_verifier::Client::new(env, &verifier_address(env));
+
+    // This call cryptographically verifies the Groth16 proof on-chain
+    // Panics (reverts) if proof is invalid — that's the Stellar contract pattern
+    verifier.verify(
+        journal_sha256,  // SHA256 of journal (public outputs)
+        image_id,        // Identifies the circuit
+        seal,            // selector(4 bytes) + Groth16 proof
+    );
+    Ok(())
+}
+
+fn verifier_address(env: &Env) -> Address {
+    env.storage().instance().get(&DataKey::Verifier).unwrap_or_else(|| {
+        Address::from_string(&soroban_sdk::String::from_str(env, NETHERMIND_VERIFIER_ID))
+    })
+}
+
+/// Dev seals prove nothing; they only bind the journal digest so local
+/// end-to-end tests exercise the same call shape as real proofs.
+fn verify_dev_seal(env: &Env, seal: &Bytes, journal_sha256: &BytesN<32>) -> Result<(), Error> {
+    let enabled: bool = env.storage().instance()
+        .get(&DataKey::DevVerifier).unwrap_or(false);
+    if !enabled {
+        return Err(Error::DevProofRejected);
+    }
+    if seal.len() != DEV_SEAL_LEN {
+        return Err(Error::InvalidProofFormat);
+    }
+    if seal.slice(4..DEV_SEAL_LEN) != Bytes::from(journal_sha256.clone()) {
+        return Err(Error::InvalidProof);
+    }
+    Ok(())
+}
+
+/// Board commitment `commit_board` expects: SHA256(board ‖ salt as big-endian u32).
+/// Same hash the replay circuit recomputes.
+fn board_commitment(env: &Env, board: &Bytes, salt: u32) -> BytesN<32> {
+    let mut preimage = board.clone();
+    preimage.extend_from_array(&salt.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+fn load_audit(env: &Env, session_id: u32) -> AuditRecord {
+    env.storage().instance().get(&DataKey::Audit(session_id)).unwrap_or(AuditRecord {
+        player1_board: None,
+        player2_board: None,
+        flags:         Vec::new(env),
+    })
+}
+
+/// The session `player_id` is moving in. Only its two players may move, and
+/// only while it is open: a move verified after the game would change the
+/// ply count and chain a replay has to match.
+fn player_session(env: &Env, session_id: u32, player_id: &Address) -> Result<GameSession, Error> {
+    let session: GameSession = env.storage().instance()
+        .get(&DataKey::GameSession(session_id)).ok_or(Error::SessionNotFound)?;
+    if *player_id != session.player1 && *player_id != session.player2 {
+        return Err(Error::NotAuthorized);
+    }
+    if !session.active || hub_closed(env, session_id) {
+        return Err(Error::GameOver);
+    }
+    Ok(session)
+}
+
+/// Whether the hub has closed the session without this contract, e.g.
+/// `expire_session` refunded it after its deadline. The session is still
+/// active here, but nothing can be reported for it any more. Hubs that keep
+/// no session records (mock-game-hub) can't be asked, and count as open.
+fn hub_closed(env: &Env, session_id: u32) -> bool {
+    let Some(game_hub) = env.storage().instance().get::<_, Address>(&DataKey::GameHub) else {
+        return false;
+    };
+    match GameHubReaderClient::new(env, &game_hub).try_get_session(&session_id) {
+        Ok(Ok(session)) => session.status != SessionStatus::Active,
+        _ => false,
+    }
+}
+
+/// The move circuit's journal for a move to (row, col) by `color`
+fn move_journal(env: &Env, row: u8, col: u8, color: u32) -> Bytes {
+    Bytes::from_array(env, &[row, 0, 0, 0, col, 0, 0, 0, color as u8, 0, 0, 0])
+}
+
+fn ply_count(env: &Env, session_id: u32, player_id: &Address) -> u32 {
+    env.storage().instance().get(&DataKey::Plies(session_id, player_id.clone())).unwrap_or(0)
+}
+
+fn add_plies(env: &Env, session_id: u32, player_id: &Address, moves: u32) {
+    let plies = ply_count(env, session_id, player_id);
+    env.storage().instance().set(&DataKey::Plies(session_id, player_id.clone()), &(plies + moves));
+}
+
+/// Fold one verified move into the player's move chain for the session. The
+/// replay circuit rebuilds the same chain, so `finalize_game` can tell the
+/// replayed moves are the ones played here.
+fn extend_move_chain(env: &Env, session_id: u32, player_id: &Address, journal_sha256: &BytesN<32>) {
+    let key = DataKey::MoveChain(session_id, player_id.clone());
+    let mut preimage = Bytes::from(move_chain(env, session_id, player_id));
+    preimage.append(&Bytes::from(journal_sha256.clone()));
+    let chain: BytesN<32> = env.crypto().sha256(&preimage).into();
+    env.storage().instance().set(&key, &chain);
+}
+
+fn move_chain(env: &Env, session_id: u32, player_id: &Address) -> BytesN<32> {
+    env.storage().instance()
+        .get(&DataKey::MoveChain(session_id, player_id.clone()))
+        .unwrap_or(BytesN::from_array(env, &[0; 32]))
+}
+
 #[contractimpl]
 impl FogOfChessContract {
     /// Initialize contract — store admin, game hub, and circuit image_id
@@ -92,14 +346,16 @@ impl FogOfChessContract {
         Ok(())
     }
 
-    /// Start a game session — calls game hub
+    /// Start a game session — calls game hub. Both players authorize it,
+    /// since the hub escrows `STAKE_POINTS` from each.
     pub fn start_game(
         env:        Env,
         session_id: u32,
         player1:    Address,
         player2:    Address,
     ) -> Result<(), Error> {
-        player1.require_auth();
+        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), STAKE_POINTS.into_val(&env)]);
+        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), STAKE_POINTS.into_val(&env)]);
 
         let session_key = DataKey::GameSession(session_id);
         if env.storage().instance().has(&session_key) {
@@ -109,13 +365,15 @@ impl FogOfChessContract {
         let game_hub: Address = env.storage().instance()
             .get(&DataKey::GameHub).ok_or(Error::NotInitialized)?;
 
-        let hub_client = game_hub::Client::new(&env, &game_hub);
+        let hub_client = GameHubClient::new(&env, &game_hub);
         hub_client.start_game(
             &env.current_contract_address(),
             &session_id, &player1, &player2,
-            &1000i128, &1000i128,
+            &STAKE_POINTS, &STAKE_POINTS,
         );
 
+        env.storage().instance().set(&DataKey::Color(session_id, player1.clone()), &WHITE);
+        env.storage().instance().set(&DataKey::Color(session_id, player2.clone()), &BLACK);
         env.storage().instance().set(&session_key, &GameSession {
             session_id, player1, player2,
             player1_won: false, active: true,
@@ -123,13 +381,15 @@ impl FogOfChessContract {
         Ok(())
     }
 
-    /// Verify a move with REAL Groth16 proof via Nethermind verifier
+    /// Verify a move in `session_id` with REAL Groth16 proof via Nethermind verifier
     pub fn verify_move(
-        env:       Env,
-        player_id: Address,
-        proof:     ZKProof,
+        env:        Env,
+        player_id:  Address,
+        session_id: u32,
+        proof:      ZKProof,
     ) -> Result<bool, Error> {
         player_id.require_auth();
+        player_session(&env, session_id, &player_id)?;
 
         // 1. Check player has committed a board
         let key = DataKey::Commitment(player_id.clone());
@@ -146,28 +406,162 @@ impl FogOfChessContract {
             return Err(Error::InvalidProof);
         }
 
-        // 3. Get the stored image_id (METHOD_ID of our chess circuit)
+        // 3. Decode the journal: the move circuit commits the side it checked
+        //    pawn direction for, and it must be the side this player plays
+        if proof.journal.len() != MOVE_JOURNAL_LEN {
+            return Err(Error::InvalidProofFormat);
+        }
+        let (row, col) = (proof.journal.get_unchecked(0), proof.journal.get_unchecked(4));
+        let color = proof.journal.get_unchecked(8) as u32;
+        if row >= 8 || col >= 8 || (color != WHITE && color != BLACK)
+            || proof.journal != move_journal(&env, row, col, color)
+        {
+            return Err(Error::InvalidProofFormat);
+        }
+        let player_color: u32 = env.storage().instance()
+            .get(&DataKey::Color(session_id, player_id.clone())).ok_or(Error::SessionNotFound)?;
+        if color != player_color {
+            return Err(Error::WrongColor);
+        }
+
+        // 4. Get the stored image_id (METHOD_ID of our chess circuit)
         let stored_image_id: BytesN<32> = env.storage().instance()
             .get(&DataKey::ImageId).ok_or(Error::NotInitialized)?;
 
-        // 4. REAL on-chain Groth16 verification via Nethermind verifier ✅
-        let verifier_id = Address::from_string(
-            &soroban_sdk::String::from_str(&env, NETHERMIND_VERIFIER_ID)
-        );
-        let verifier = risc0_verifier::Client::new(&env, &verifier_id);
-
-        // This call cryptographically verifies the Groth16 proof on-chain
-        // Panics (reverts) if proof is invalid — that's the Stellar contract pattern
-        verifier.verify(
-            &proof.journal_sha256,  // SHA256 of journal (public outputs)
-            &stored_image_id,       // Identifies our chess circuit
-            &proof.seal,            // selector(4 bytes) + Groth16 proof
-        );
+        // 5. REAL on-chain Groth16 verification via Nethermind verifier ✅
+        //    The contract hashes the journal itself, so the seal is bound to
+        //    the move and color checked above
+        let journal_sha256: BytesN<32> = env.crypto().sha256(&proof.journal).into();
+        check_seal(&env, &proof.seal, &journal_sha256, &stored_image_id)?;
 
+        add_plies(&env, session_id, &player_id, 1);
+        extend_move_chain(&env, session_id, &player_id, &journal_sha256);
         Ok(true)
     }
 
-    /// End game session — calls game hub
+    /// Verify several moves at once with one aggregated proof from the
+    /// prover's /prove-batch. The batch circuit only accepts moves that each
+    /// carry a receipt from our move circuit, so one seal covers every ply.
+    /// Returns the number of moves verified.
+    pub fn verify_moves(
+        env:        Env,
+        player_id:  Address,
+        session_id: u32,
+        proof:      BatchProof,
+    ) -> Result<u32, Error> {
+        player_id.require_auth();
+
+        // 1. Check player has committed a board
+        if !env.storage().instance().has(&DataKey::Commitment(player_id.clone())) {
+            return Err(Error::NoCommitment);
+        }
+
+        let move_image_id: BytesN<32> = env.storage().instance()
+            .get(&DataKey::ImageId).ok_or(Error::NotInitialized)?;
+        let batch_image_id: BytesN<32> = env.storage().instance()
+            .get(&DataKey::BatchImageId).ok_or(Error::NotInitialized)?;
+        player_session(&env, session_id, &player_id)?;
+
+        let player_color: u32 = env.storage().instance()
+            .get(&DataKey::Color(session_id, player_id.clone())).ok_or(Error::SessionNotFound)?;
+
+        // 2. Parse the journal — moves must come from *our* move circuit
+        let len = proof.journal.len();
+        if len < BATCH_HEADER_LEN + 2 || !(len - BATCH_HEADER_LEN).is_multiple_of(2) {
+            return Err(Error::InvalidProofFormat);
+        }
+        let moves = (len - BATCH_HEADER_LEN) / 2;
+        if moves > MAX_BATCH_MOVES {
+            return Err(Error::InvalidProofFormat);
+        }
+        if proof.journal.slice(0..32) != Bytes::from(move_image_id) {
+            return Err(Error::InvalidProof);
+        }
+        // The move circuit commits the side it checked pawn direction for;
+        // it must be the side this player actually plays
+        let color = proof.journal.get_unchecked(32) as u32;
+        if color != WHITE && color != BLACK {
+            return Err(Error::InvalidProofFormat);
+        }
+        if color != player_color {
+            return Err(Error::WrongColor);
+        }
+        for byte in proof.journal.slice(BATCH_HEADER_LEN..len).iter() {
+            if byte >= 8 {
+                return Err(Error::InvalidProofFormat); // end_pos off the board
+            }
+        }
+
+        // 3. The contract hashes the journal itself, so the seal is bound to
+        //    exactly the moves parsed above
+        let journal_sha256: BytesN<32> = env.crypto().sha256(&proof.journal).into();
+        check_seal(&env, &proof.seal, &journal_sha256, &batch_image_id)?;
+
+        // 4. Chain each move's own journal, (end_pos, color) through risc0
+        //    serde, as if it had come through `verify_move`
+        for i in 0..moves {
+            let at = BATCH_HEADER_LEN + i * 2;
+            let (row, col) = (proof.journal.get_unchecked(at), proof.journal.get_unchecked(at + 1));
+            let journal = move_journal(&env, row, col, color);
+            extend_move_chain(&env, session_id, &player_id, &env.crypto().sha256(&journal).into());
+        }
+        add_plies(&env, session_id, &player_id, moves);
+        Ok(moves)
+    }
+
+    /// Admin only — set the BATCH_ID of the aggregation circuit used by `verify_moves`
+    pub fn set_batch_image_id(env: Env, image_id: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::BatchImageId, &image_id);
+        Ok(())
+    }
+
+    /// Moves verified for `player_id` in `session_id` so far, single or batched
+    pub fn get_ply_count(env: Env, session_id: u32, player_id: Address) -> u32 {
+        ply_count(&env, session_id, &player_id)
+    }
+
+    /// Chain over every move verified for `player_id` in `session_id`, what a
+    /// replay proof has to reproduce
+    pub fn get_move_chain(env: Env, session_id: u32, player_id: Address) -> BytesN<32> {
+        move_chain(&env, session_id, &player_id)
+    }
+
+    /// Admin only — accept dev-mode seals in `verify_move`. For local and
+    /// testnet deployments driven by a dev-mode prover; leave off in production.
+    pub fn set_dev_verifier(env: Env, enabled: bool) -> Result<(), Error> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::DevVerifier, &enabled);
+        Ok(())
+    }
+
+    pub fn is_dev_verifier(env: Env) -> bool {
+        env.storage().instance().get(&DataKey::DevVerifier).unwrap_or(false)
+    }
+
+    /// Admin only — point Groth16 seals at another verifier deployment, e.g.
+    /// on a network where the Nethermind verifier lives at a different address
+    pub fn set_verifier(env: Env, verifier: Address) -> Result<(), Error> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Verifier, &verifier);
+        Ok(())
+    }
+
+    pub fn get_verifier(env: Env) -> Address {
+        verifier_address(&env)
+    }
+
+    /// Report how the game ended. The first report is only a claim: the hub
+    /// is told once the other player reports the same result, or once
+    /// `CHALLENGE_LEDGERS` pass and `settle_game` is called. Reporting the
+    /// other result challenges the claim, and then only a replay proof
+    /// through `finalize_game` decides the game.
     pub fn end_game(
         env:        Env,
         caller:     Address,
@@ -183,19 +577,326 @@ impl FogOfChessContract {
         if caller != session.player1 && caller != session.player2 {
             return Err(Error::NotAuthorized);
         }
+        // A replay-proven result is final
+        if env.storage().instance().has(&DataKey::Finalized(session_id)) {
+            return Err(Error::AlreadyFinalized);
+        }
+        if !session.active || hub_closed(&env, session_id) {
+            return Err(Error::GameOver);
+        }
+
+        let claim_key = DataKey::Claim(session_id);
+        let Some(mut claim) = env.storage().instance().get::<_, ResultClaim>(&claim_key) else {
+            let deadline = env.ledger().sequence()
+                .checked_add(CHALLENGE_LEDGERS).ok_or(Error::DeadlineOverflow)?;
+            let claim = ResultClaim { claimed_by: caller, player1_won, deadline, challenged: false };
+            env.storage().instance().set(&claim_key, &claim);
+            return Ok(());
+        };
+        if caller == claim.claimed_by {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        // Both players report the same result: settle it now
+        if player1_won == claim.player1_won {
+            let game_hub: Address = env.storage().instance()
+                .get(&DataKey::GameHub).ok_or(Error::NotInitialized)?;
+            let hub_client = GameHubClient::new(&env, &game_hub);
+            hub_client.end_game(&session_id, &player1_won);
+
+            session.active      = false;
+            session.player1_won = player1_won;
+            env.storage().instance().set(&session_key, &session);
+            return Ok(());
+        }
+
+        if claim.challenged {
+            return Err(Error::AlreadyClaimed);
+        }
+        if env.ledger().sequence() > claim.deadline {
+            return Err(Error::ChallengeClosed);
+        }
+        claim.challenged = true;
+        env.storage().instance().set(&claim_key, &claim);
+        Ok(())
+    }
+
+    /// Settle a claimed result once its challenge window has closed. Anyone
+    /// may call. An unchallenged claim is reported to the hub as claimed; a
+    /// challenged one nobody settled with a replay proof is cancelled, and
+    /// the hub refunds both players. Returns whether the hub was told a winner.
+    pub fn settle_game(env: Env, session_id: u32) -> Result<bool, Error> {
+        let session_key = DataKey::GameSession(session_id);
+        let mut session: GameSession = env.storage().instance()
+            .get(&session_key).ok_or(Error::SessionNotFound)?;
+        if env.storage().instance().has(&DataKey::Finalized(session_id)) {
+            return Err(Error::AlreadyFinalized);
+        }
+        if !session.active || hub_closed(&env, session_id) {
+            return Err(Error::GameOver);
+        }
+        let claim: ResultClaim = env.storage().instance()
+            .get(&DataKey::Claim(session_id)).ok_or(Error::NoClaim)?;
+        if env.ledger().sequence() <= claim.deadline {
+            return Err(Error::ChallengeOpen);
+        }
 
         let game_hub: Address = env.storage().instance()
             .get(&DataKey::GameHub).ok_or(Error::NotInitialized)?;
+        let hub_client = GameHubClient::new(&env, &game_hub);
+        if claim.challenged {
+            hub_client.end_game_with_outcome(&session_id, &Outcome::Cancelled);
+        } else {
+            hub_client.end_game(&session_id, &claim.player1_won);
+            session.player1_won = claim.player1_won;
+        }
+
+        session.active = false;
+        env.storage().instance().set(&session_key, &session);
+        Ok(!claim.challenged)
+    }
+
+    /// The result claimed for `session_id`, if any
+    pub fn get_claim(env: Env, session_id: u32) -> Option<ResultClaim> {
+        env.storage().instance().get(&DataKey::Claim(session_id))
+    }
+
+    /// Push the hub's deadline for a running game `ledgers` past the current
+    /// ledger, so a long game isn't expired under the players. Either player
+    /// may ask, up to `MAX_EXTENSIONS_PER_PLAYER` times a session and at most
+    /// `get_max_extension` ledgers at a time. Turns aren't tracked on-chain,
+    /// so the hub is never told who it is waiting on and an expired game is
+    /// refunded rather than forfeited.
+    pub fn extend_deadline(
+        env:        Env,
+        caller:     Address,
+        session_id: u32,
+        ledgers:    u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let session: GameSession = env.storage().instance()
+            .get(&DataKey::GameSession(session_id)).ok_or(Error::SessionNotFound)?;
+        if caller != session.player1 && caller != session.player2 {
+            return Err(Error::NotAuthorized);
+        }
+        if !session.active || hub_closed(&env, session_id) {
+            return Err(Error::GameOver);
+        }
+        if ledgers > Self::get_max_extension(env.clone()) {
+            return Err(Error::ExtensionTooLong);
+        }
+        let used_key = DataKey::Extensions(session_id, caller);
+        let used: u32 = env.storage().instance().get(&used_key).unwrap_or(0);
+        if used >= MAX_EXTENSIONS_PER_PLAYER {
+            return Err(Error::NoExtensionsLeft);
+        }
+        let deadline = env.ledger().sequence().checked_add(ledgers).ok_or(Error::DeadlineOverflow)?;
+
+        let game_hub: Address = env.storage().instance()
+            .get(&DataKey::GameHub).ok_or(Error::NotInitialized)?;
+        let hub_client = GameHubClient::new(&env, &game_hub);
+        hub_client.extend_deadline(&session_id, &deadline, &None);
+
+        env.storage().instance().set(&used_key, &(used + 1));
+        Ok(())
+    }
+
+    /// Admin only — the most ledgers one `extend_deadline` call may add
+    pub fn set_max_extension(env: Env, ledgers: u32) -> Result<(), Error> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::MaxExtension, &ledgers);
+        Ok(())
+    }
+
+    pub fn get_max_extension(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::MaxExtension).unwrap_or(DEFAULT_MAX_EXTENSION)
+    }
+
+    /// Settle a game with a replay proof from the prover's /prove-replay: both
+    /// revealed boards match the players' commitments, every move was legal,
+    /// and the moves are exactly the ones verified here during play. Overrides
+    /// any result claimed with `end_game` but not yet settled; once the hub
+    /// has paid out it can't settle the game again. Returns `player1_won`.
+    pub fn finalize_game(
+        env:        Env,
+        caller:     Address,
+        session_id: u32,
+        proof:      ReplayProof,
+    ) -> Result<bool, Error> {
+        caller.require_auth();
+
+        let session_key = DataKey::GameSession(session_id);
+        let mut session: GameSession = env.storage().instance()
+            .get(&session_key).ok_or(Error::SessionNotFound)?;
+
+        if caller != session.player1 && caller != session.player2 {
+            return Err(Error::NotAuthorized);
+        }
+        if env.storage().instance().has(&DataKey::Finalized(session_id)) {
+            return Err(Error::AlreadyFinalized);
+        }
+        if !session.active || hub_closed(&env, session_id) {
+            return Err(Error::GameOver);
+        }
+
+        let replay_image_id: BytesN<32> = env.storage().instance()
+            .get(&DataKey::ReplayImageId).ok_or(Error::NotInitialized)?;
+
+        // 1. The replay must start from the boards both players committed
+        if proof.journal.len() != REPLAY_JOURNAL_LEN {
+            return Err(Error::InvalidProofFormat);
+        }
+        let white: BytesN<32> = env.storage().instance()
+            .get(&DataKey::Commitment(session.player1.clone())).ok_or(Error::NoCommitment)?;
+        let black: BytesN<32> = env.storage().instance()
+            .get(&DataKey::Commitment(session.player2.clone())).ok_or(Error::NoCommitment)?;
+        if proof.journal.slice(0..32) != Bytes::from(white)
+            || proof.journal.slice(32..64) != Bytes::from(black)
+        {
+            return Err(Error::InvalidProof);
+        }
+
+        // 2. ...and replay the moves the players proved here, in order
+        let plies = u32::from_be_bytes([
+            proof.journal.get_unchecked(128),
+            proof.journal.get_unchecked(129),
+            proof.journal.get_unchecked(130),
+            proof.journal.get_unchecked(131),
+        ]);
+        if plies != ply_count(&env, session_id, &session.player1) + ply_count(&env, session_id, &session.player2)
+            || proof.journal.slice(64..96) != Bytes::from(move_chain(&env, session_id, &session.player1))
+            || proof.journal.slice(96..128) != Bytes::from(move_chain(&env, session_id, &session.player2))
+        {
+            return Err(Error::ReplayMismatch);
+        }
+
+        // 3. A replay that never took a king can't settle anything
+        let player1_won = match proof.journal.get_unchecked(REPLAY_JOURNAL_LEN - 1) {
+            REPLAY_WHITE_WINS => true,
+            REPLAY_BLACK_WINS => false,
+            _ => return Err(Error::GameUndecided),
+        };
 
-        let hub_client = game_hub::Client::new(&env, &game_hub);
+        // 4. Seal over the journal the contract hashed itself
+        let journal_sha256: BytesN<32> = env.crypto().sha256(&proof.journal).into();
+        check_seal(&env, &proof.seal, &journal_sha256, &replay_image_id)?;
+
+        // 5. Report the proven result to the hub
+        let game_hub: Address = env.storage().instance()
+            .get(&DataKey::GameHub).ok_or(Error::NotInitialized)?;
+        let hub_client = GameHubClient::new(&env, &game_hub);
         hub_client.end_game(&session_id, &player1_won);
 
         session.active      = false;
         session.player1_won = player1_won;
         env.storage().instance().set(&session_key, &session);
+        env.storage().instance().set(&DataKey::Finalized(session_id), &true);
+        Ok(player1_won)
+    }
+
+    /// Admin only — set the REPLAY_ID of the whole-game circuit used by `finalize_game`
+    pub fn set_replay_image_id(env: Env, image_id: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env.storage().instance()
+            .get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::ReplayImageId, &image_id);
         Ok(())
     }
 
+    pub fn is_finalized(env: Env, session_id: u32) -> bool {
+        env.storage().instance().has(&DataKey::Finalized(session_id))
+    }
+
+    /// Reveal the board a player committed to, once the game is over. The
+    /// board and salt must hash to the player's stored commitment; the
+    /// revealed setup is then public in `get_audit`.
+    pub fn reveal_board(
+        env:        Env,
+        player_id:  Address,
+        session_id: u32,
+        board:      Bytes,
+        salt:       u32,
+    ) -> Result<(), Error> {
+        player_id.require_auth();
+
+        let session: GameSession = env.storage().instance()
+            .get(&DataKey::GameSession(session_id)).ok_or(Error::SessionNotFound)?;
+        if session.active && !hub_closed(&env, session_id) {
+            return Err(Error::GameNotOver);
+        }
+        let is_player1 = player_id == session.player1;
+        if !is_player1 && player_id != session.player2 {
+            return Err(Error::NotAuthorized);
+        }
+
+        // 1. Only a well-formed board can be audited
+        if board.len() != BOARD_SQUARES || board.iter().any(|piece| piece > MAX_PIECE) {
+            return Err(Error::InvalidBoard);
+        }
+
+        // 2. It must be exactly the board the player committed to
+        let commitment: BytesN<32> = env.storage().instance()
+            .get(&DataKey::Commitment(player_id)).ok_or(Error::NoCommitment)?;
+        if board_commitment(&env, &board, salt) != commitment {
+            return Err(Error::CommitmentMismatch);
+        }
+
+        let mut audit = load_audit(&env, session_id);
+        let slot = if is_player1 { &mut audit.player1_board } else { &mut audit.player2_board };
+        if slot.is_some() {
+            return Err(Error::AlreadyRevealed);
+        }
+        *slot = Some(board);
+        env.storage().instance().set(&DataKey::Audit(session_id), &audit);
+        Ok(())
+    }
+
+    /// Flag a square where the opponent's revealed board disagrees with what
+    /// happened in play. Flags are a public record only — `finalize_game`
+    /// with a replay proof is what settles the dispute.
+    pub fn flag_mismatch(
+        env:        Env,
+        flagger:    Address,
+        session_id: u32,
+        square:     u32,
+    ) -> Result<(), Error> {
+        flagger.require_auth();
+
+        let session: GameSession = env.storage().instance()
+            .get(&DataKey::GameSession(session_id)).ok_or(Error::SessionNotFound)?;
+        let is_player1 = flagger == session.player1;
+        if !is_player1 && flagger != session.player2 {
+            return Err(Error::NotAuthorized);
+        }
+        if square >= BOARD_SQUARES {
+            return Err(Error::InvalidBoard);
+        }
+
+        let mut audit = load_audit(&env, session_id);
+        let opponent_board = if is_player1 { &audit.player2_board } else { &audit.player1_board };
+        if opponent_board.is_none() {
+            return Err(Error::NotRevealed);
+        }
+        if audit.flags.iter().any(|f| f.flagged_by == flagger && f.square == square) {
+            return Err(Error::AlreadyFlagged);
+        }
+
+        audit.flags.push_back(MismatchFlag {
+            flagged_by: flagger,
+            square,
+            ledger:     env.ledger().sequence(),
+        });
+        env.storage().instance().set(&DataKey::Audit(session_id), &audit);
+        Ok(())
+    }
+
+    pub fn get_audit(env: Env, session_id: u32) -> Option<AuditRecord> {
+        env.storage().instance().get(&DataKey::Audit(session_id))
+    }
+
     pub fn get_commitment(env: Env, player_id: Address) -> Option<BytesN<32>> {
         env.storage().instance().get(&DataKey::Commitment(player_id))
     }
@@ -203,4 +904,10 @@ impl FogOfChessContract {
     pub fn get_session(env: Env, session_id: u32) -> Option<GameSession> {
         env.storage().instance().get(&DataKey::GameSession(session_id))
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test;
+
+// Needs the built verifier and mock hub wasm files; see Cargo.toml
+#[cfg(all(test, feature = "wasm-import"))]
+mod test_wasm;
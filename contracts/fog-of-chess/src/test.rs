@@ -0,0 +1,1034 @@
+#![cfg(test)]
+
+// Unit tests for the fog-of-chess contract. The Game Hub is the shared
+// RecordingGameHub from game-hub-client and the Groth16 verifier is a small
+// test contract that accepts or rejects whatever it is told to, so the tests
+// build and run without any prebuilt wasm. Boards, commitments and replay
+// journals come from fog-chess-core, the same code the prover uses.
+//
+// For tests against the built verifier and mock hub wasm, see test_wasm.rs
+// (`--features wasm-import`).
+
+use crate::{Error, FogOfChessContract, FogOfChessContractClient, ReplayProof, BatchProof, ZKProof};
+use crate::{CHALLENGE_LEDGERS, DEV_SELECTOR, GROTH16_SELECTOR, GROTH16_SEAL_LEN, NETHERMIND_VERIFIER_ID};
+use fog_chess_core::board::Board;
+use fog_chess_core::commitment::{board_commitment, extend_move_chain, move_chain};
+use fog_chess_core::journal::{encode_move_journal, GameResult, ReplayJournal};
+use fog_chess_core::piece::{Color, Square};
+use game_hub::{ExpiryRule, GameHub, GameHubClient};
+use game_hub_client::Outcome;
+use game_hub_client::testutils::{HubCall, RecordingGameHub, RecordingGameHubClient, StartGameCall};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Ledger as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
+};
+
+const WHITE_SALT: u32 = 1234;
+const BLACK_SALT: u32 = 5678;
+
+// ============================================================================
+// Test Verifier
+// ============================================================================
+
+#[contracttype]
+pub enum TestVerifierKey {
+    Reject,
+    Verified,
+}
+
+/// Stands in for the Nethermind verifier. Records the journal digest and
+/// image id of every seal it accepts; panics like a failed proof check once
+/// told to reject.
+#[contract]
+pub struct TestVerifier;
+
+#[contractimpl]
+impl TestVerifier {
+    pub fn verify(env: Env, journal: BytesN<32>, image_id: BytesN<32>, _seal: Bytes) {
+        if env.storage().instance().get(&TestVerifierKey::Reject).unwrap_or(false) {
+            panic!("invalid proof");
+        }
+        let mut verified = Self::verified(env.clone());
+        verified.push_back((journal, image_id));
+        env.storage().instance().set(&TestVerifierKey::Verified, &verified);
+    }
+
+    pub fn set_reject(env: Env, reject: bool) {
+        env.storage().instance().set(&TestVerifierKey::Reject, &reject);
+    }
+
+    pub fn verified(env: Env) -> Vec<(BytesN<32>, BytesN<32>)> {
+        env.storage().instance().get(&TestVerifierKey::Verified).unwrap_or_else(|| Vec::new(&env))
+    }
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+struct Setup {
+    env: Env,
+    client: FogOfChessContractClient<'static>,
+    hub: RecordingGameHubClient<'static>,
+    verifier: TestVerifierClient<'static>,
+    player1: Address,
+    player2: Address,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let hub = RecordingGameHubClient::new(&env, &env.register(RecordingGameHub, ()));
+    let verifier = TestVerifierClient::new(&env, &env.register(TestVerifier, ()));
+
+    let admin = Address::generate(&env);
+    let client = FogOfChessContractClient::new(&env, &env.register(FogOfChessContract, ()));
+    client.init(&admin, &hub.address, &image_id(&env, 1));
+    client.set_batch_image_id(&image_id(&env, 2));
+    client.set_replay_image_id(&image_id(&env, 3));
+    client.set_verifier(&verifier.address);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    Setup { env, client, hub, verifier, player1, player2 }
+}
+
+fn image_id(env: &Env, tag: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[tag; 32])
+}
+
+fn groth16_seal(env: &Env) -> Bytes {
+    let mut seal = Bytes::from_array(env, &GROTH16_SELECTOR);
+    seal.extend_from_slice(&[0u8; (GROTH16_SEAL_LEN - 4) as usize]);
+    seal
+}
+
+fn dev_seal(env: &Env, journal_sha256: &BytesN<32>) -> Bytes {
+    let mut seal = Bytes::from_array(env, &DEV_SELECTOR);
+    seal.append(&Bytes::from(journal_sha256.clone()));
+    seal
+}
+
+/// The move circuit's journal for white's e4
+fn e4_journal(env: &Env) -> Bytes {
+    Bytes::from_array(env, &encode_move_journal(Square::from_pos([4, 4]).unwrap(), Color::White))
+}
+
+/// A move proof of white's e4 for `player`'s stored commitment
+fn move_proof(env: &Env, commitment: &BytesN<32>, seal: Bytes) -> ZKProof {
+    ZKProof {
+        seal,
+        journal: e4_journal(env),
+        image_id: image_id(env, 1),
+        public_inputs: vec![env, commitment.clone()],
+    }
+}
+
+/// A batch journal from the move circuit (image id tag 1): color, then end squares
+fn batch_proof(env: &Env, color: u8, end_positions: &[u8]) -> BatchProof {
+    let mut journal = Bytes::from(image_id(env, 1));
+    journal.push_back(color);
+    journal.extend_from_slice(end_positions);
+    BatchProof { seal: groth16_seal(env), journal }
+}
+
+/// Commit the starting layout for both players: player 1 plays white
+fn commit_initial_boards(s: &Setup) -> (Bytes, Bytes) {
+    let board = Board::initial();
+    let white = Bytes::from_slice(&s.env, board.side(Color::White));
+    let black = Bytes::from_slice(&s.env, board.side(Color::Black));
+    let white_commitment = board_commitment(board.side(Color::White), WHITE_SALT);
+    let black_commitment = board_commitment(board.side(Color::Black), BLACK_SALT);
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &white_commitment));
+    s.client.commit_board(&s.player2, &BytesN::from_array(&s.env, &black_commitment));
+    (white, black)
+}
+
+/// Both players report the same result, which settles the game at the hub
+fn agree_result(s: &Setup, session_id: u32, player1_won: bool) {
+    s.client.end_game(&s.player1, &session_id, &player1_won);
+    s.client.end_game(&s.player2, &session_id, &player1_won);
+}
+
+/// Move past the challenge window of a result claimed now
+fn pass_challenge_window(s: &Setup) {
+    s.env.ledger().with_mut(|ledger| ledger.sequence_number += CHALLENGE_LEDGERS + 1);
+}
+
+/// Verify one batch of end squares for each side, as the game was played
+fn play(s: &Setup, session_id: u32, white_ends: &[u8], black_ends: &[u8]) {
+    s.client.verify_moves(&s.player1, &session_id, &batch_proof(&s.env, 0, white_ends));
+    s.client.verify_moves(&s.player2, &session_id, &batch_proof(&s.env, 1, black_ends));
+}
+
+fn ends(squares: &[u8]) -> impl Iterator<Item = Square> + '_ {
+    squares.chunks(2).map(|pos| Square::from_pos([pos[0], pos[1]]).unwrap())
+}
+
+/// The replay journal of a game over the committed starting boards in which
+/// white and black moved to these end squares
+fn replay_journal(white_ends: &[u8], black_ends: &[u8], result: GameResult) -> ReplayJournal {
+    let board = Board::initial();
+    ReplayJournal {
+        white_commitment: board_commitment(board.side(Color::White), WHITE_SALT),
+        black_commitment: board_commitment(board.side(Color::Black), BLACK_SALT),
+        white_moves: move_chain(ends(white_ends), Color::White),
+        black_moves: move_chain(ends(black_ends), Color::Black),
+        plies: ((white_ends.len() + black_ends.len()) / 2) as u32,
+        result,
+    }
+}
+
+/// A dev-sealed replay proof over `journal`
+fn replay_proof(env: &Env, journal: &ReplayJournal) -> ReplayProof {
+    let journal = Bytes::from_array(env, &journal.encode());
+    let journal_sha256: BytesN<32> = env.crypto().sha256(&journal).into();
+    ReplayProof { seal: dev_seal(env, &journal_sha256), journal }
+}
+
+// A short game both replay tests play: white e4 and Qh5, black e5
+const WHITE_ENDS: &[u8] = &[4, 4, 3, 7];
+const BLACK_ENDS: &[u8] = &[3, 4];
+
+/// Assert that a Result contains a specific fog_of_chess error
+fn assert_fog_of_chess_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Session Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_reports_session_to_hub() {
+    let s = setup_test();
+
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let start = StartGameCall {
+        game_id: s.client.address.clone(),
+        session_id: 1,
+        player1: s.player1.clone(),
+        player2: s.player2.clone(),
+        player1_points: 1000,
+        player2_points: 1000,
+    };
+    assert_eq!(s.hub.calls(), vec![&s.env, HubCall::StartGame(start)]);
+
+    let result = s.client.try_start_game(&1u32, &s.player1, &s.player2);
+    assert_fog_of_chess_error(&result, Error::SessionExists);
+}
+
+#[test]
+fn test_start_game_needs_both_players() {
+    let s = setup_test();
+
+    // Each player signs for the session and their own stake
+    s.client.start_game(&2u32, &s.player1, &s.player2);
+    let stake_args: Vec<Val> = (2u32, 1000i128).into_val(&s.env);
+    for player in [&s.player1, &s.player2] {
+        assert!(s.env.auths().iter().any(|(address, invocation)| address == player
+            && invocation.function
+                == AuthorizedFunction::Contract((
+                    s.client.address.clone(),
+                    Symbol::new(&s.env, "start_game"),
+                    stake_args.clone(),
+                ))));
+    }
+
+    // player1 alone can't put player2's points in escrow
+    s.env.mock_auths(&[MockAuth {
+        address: &s.player1,
+        invoke: &MockAuthInvoke {
+            contract: &s.client.address,
+            fn_name: "start_game",
+            args: (3u32, 1000i128).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(s.client.try_start_game(&3u32, &s.player1, &s.player2).is_err());
+    assert_eq!(s.hub.calls_for(&3u32).len(), 0);
+    assert!(s.client.get_session(&3u32).is_none());
+}
+
+#[test]
+fn test_end_game_reports_result_to_hub() {
+    let s = setup_test();
+
+    s.client.start_game(&2u32, &s.player1, &s.player2);
+
+    let outsider = Address::generate(&s.env);
+    let result = s.client.try_end_game(&outsider, &2u32, &true);
+    assert_fog_of_chess_error(&result, Error::NotAuthorized);
+
+    // One player's word is only a claim...
+    s.client.end_game(&s.player2, &2u32, &false);
+    assert_eq!(s.hub.calls_for(&2u32).len(), 1);
+    assert!(s.client.get_session(&2u32).unwrap().active);
+    let result = s.client.try_end_game(&s.player2, &2u32, &false);
+    assert_fog_of_chess_error(&result, Error::AlreadyClaimed);
+
+    // ...that the hub hears once the other player agrees
+    s.client.end_game(&s.player1, &2u32, &false);
+    assert_eq!(s.hub.player1_won(&2u32), Some(false));
+
+    let session = s.client.get_session(&2u32).unwrap();
+    assert!(!session.active);
+    assert!(!session.player1_won);
+    let result = s.client.try_end_game(&s.player1, &2u32, &true);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+}
+
+#[test]
+fn test_unchallenged_claim_settles_after_window() {
+    let s = setup_test();
+    s.client.start_game(&6u32, &s.player1, &s.player2);
+
+    let result = s.client.try_settle_game(&6u32);
+    assert_fog_of_chess_error(&result, Error::NoClaim);
+
+    s.client.end_game(&s.player1, &6u32, &true);
+    let result = s.client.try_settle_game(&6u32);
+    assert_fog_of_chess_error(&result, Error::ChallengeOpen);
+
+    // Once the window has passed the claim stands, and anyone may settle it
+    pass_challenge_window(&s);
+    let result = s.client.try_end_game(&s.player2, &6u32, &false);
+    assert_fog_of_chess_error(&result, Error::ChallengeClosed);
+    assert!(s.client.settle_game(&6u32));
+    assert_eq!(s.hub.player1_won(&6u32), Some(true));
+    assert!(s.client.get_session(&6u32).unwrap().player1_won);
+
+    let result = s.client.try_settle_game(&6u32);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+}
+
+#[test]
+fn test_unproven_challenge_is_refunded() {
+    let s = setup_test();
+    s.client.start_game(&7u32, &s.player1, &s.player2);
+
+    s.client.end_game(&s.player1, &7u32, &true);
+    s.client.end_game(&s.player2, &7u32, &false);
+    assert!(s.client.get_claim(&7u32).unwrap().challenged);
+    let result = s.client.try_end_game(&s.player2, &7u32, &false);
+    assert_fog_of_chess_error(&result, Error::AlreadyClaimed);
+
+    // Nobody proved a winner, so neither player's stake moves
+    pass_challenge_window(&s);
+    assert!(!s.client.settle_game(&7u32));
+    assert_eq!(s.hub.calls_for(&7u32).get(1), Some(HubCall::EndGameWithOutcome(7, Outcome::Cancelled)));
+    assert!(!s.client.get_session(&7u32).unwrap().active);
+}
+
+#[test]
+fn test_failed_hub_end_keeps_game_active() {
+    let s = setup_test();
+
+    s.client.start_game(&3u32, &s.player1, &s.player2);
+
+    s.client.end_game(&s.player1, &3u32, &true);
+    s.hub.set_failing(&true);
+    assert!(s.client.try_end_game(&s.player2, &3u32, &true).is_err());
+    assert!(s.client.get_session(&3u32).unwrap().active);
+
+    s.hub.set_failing(&false);
+    s.client.end_game(&s.player2, &3u32, &true);
+    assert_eq!(s.hub.player1_won(&3u32), Some(true));
+}
+
+#[test]
+fn test_extend_deadline_forwards_to_hub() {
+    let s = setup_test();
+
+    s.client.start_game(&4u32, &s.player1, &s.player2);
+    s.client.extend_deadline(&s.player2, &4u32, &500);
+    assert_eq!(s.hub.calls_for(&4u32).get(1), Some(HubCall::ExtendDeadline(4, 600, None)));
+
+    agree_result(&s, 4, true);
+    let result = s.client.try_extend_deadline(&s.player1, &4u32, &500);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+}
+
+#[test]
+fn test_extend_deadline_is_bounded() {
+    let s = setup_test();
+    s.client.start_game(&5u32, &s.player1, &s.player2);
+
+    // One call can't push the deadline past the cap...
+    let max = s.client.get_max_extension();
+    let result = s.client.try_extend_deadline(&s.player1, &5u32, &(max + 1));
+    assert_fog_of_chess_error(&result, Error::ExtensionTooLong);
+    s.client.extend_deadline(&s.player1, &5u32, &max);
+
+    // ...and each player only gets a few calls a session
+    s.client.extend_deadline(&s.player1, &5u32, &100);
+    s.client.extend_deadline(&s.player1, &5u32, &100);
+    let result = s.client.try_extend_deadline(&s.player1, &5u32, &100);
+    assert_fog_of_chess_error(&result, Error::NoExtensionsLeft);
+    s.client.extend_deadline(&s.player2, &5u32, &100);
+    assert_eq!(s.hub.calls_for(&5u32).len(), 5);
+
+    // A deadline past the last ledger is an error, not a wrapped u32
+    s.client.set_max_extension(&u32::MAX);
+    s.env.ledger().with_mut(|ledger| {
+        ledger.sequence_number = u32::MAX - 10;
+        ledger.min_persistent_entry_ttl = 1;
+        ledger.min_temp_entry_ttl = 1;
+        ledger.max_entry_ttl = 5;
+    });
+    let result = s.client.try_extend_deadline(&s.player2, &5u32, &11);
+    assert_fog_of_chess_error(&result, Error::DeadlineOverflow);
+    s.client.extend_deadline(&s.player2, &5u32, &10);
+    assert_eq!(s.hub.calls_for(&5u32).last(), Some(HubCall::ExtendDeadline(5, u32::MAX, None)));
+}
+
+#[test]
+fn test_session_the_hub_expired_is_over() {
+    let s = setup_test();
+
+    // The real hub, which keeps session records and expires sessions
+    let admin = Address::generate(&s.env);
+    let hub = GameHubClient::new(&s.env, &s.env.register(GameHub, (&admin,)));
+    let client = FogOfChessContractClient::new(&s.env, &s.env.register(FogOfChessContract, ()));
+    client.init(&admin, &hub.address, &image_id(&s.env, 1));
+    client.set_replay_image_id(&image_id(&s.env, 3));
+    hub.add_game(&client.address, &String::from_str(&s.env, "Lantern Chess"), &1);
+    hub.set_expiry_policy(&client.address, &1_000, &ExpiryRule::Refund);
+    hub.credit_points(&s.player1, &1000);
+    hub.credit_points(&s.player2, &1000);
+
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    client.commit_board(&s.player1, &commitment);
+    client.start_game(&8u32, &s.player1, &s.player2);
+    client.end_game(&s.player1, &8u32, &true);
+
+    // Nobody settled in time, so the hub refunds both stakes on its own
+    s.env.ledger().with_mut(|ledger| ledger.sequence_number += 1_001);
+    hub.expire_session(&8u32);
+    assert_eq!(hub.get_balance(&s.player1), 1000);
+    assert_eq!(hub.get_balance(&s.player2), 1000);
+
+    // The game here is over too, rather than failing at the hub
+    let result = client.try_extend_deadline(&s.player1, &8u32, &500);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    let result = client.try_end_game(&s.player2, &8u32, &true);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    let result = client.try_settle_game(&8u32);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins));
+    let result = client.try_finalize_game(&s.player1, &8u32, &proof);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    let result = client.try_verify_move(&s.player1, &8u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env)));
+    assert_fog_of_chess_error(&result, Error::GameOver);
+
+    // ...and boards can be revealed
+    let result = client.try_reveal_board(&s.player1, &8u32, &Bytes::from_array(&s.env, &[0u8; 64]), &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::CommitmentMismatch);
+}
+
+// ============================================================================
+// Proof Verification Tests
+// ============================================================================
+
+#[test]
+fn test_groth16_seal_goes_to_verifier() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+    assert!(s.client.verify_move(&s.player1, &1u32, &proof));
+
+    let journal_sha256: BytesN<32> = s.env.crypto().sha256(&proof.journal).into();
+    assert_eq!(s.verifier.verified(), vec![&s.env, (journal_sha256, image_id(&s.env, 1))]);
+    assert_eq!(s.client.get_ply_count(&1u32, &s.player1), 1);
+}
+
+#[test]
+fn test_rejected_groth16_seal_counts_no_move() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    s.verifier.set_reject(&true);
+    let proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+    assert!(s.client.try_verify_move(&s.player1, &1u32, &proof).is_err());
+    assert_eq!(s.client.get_ply_count(&1u32, &s.player1), 0);
+}
+
+#[test]
+fn test_verify_move_checks_commitment_and_seal_format() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+    let result = s.client.try_verify_move(&s.player1, &1u32, &proof);
+    assert_fog_of_chess_error(&result, Error::NoCommitment);
+
+    s.client.commit_board(&s.player1, &commitment);
+    let other = BytesN::from_array(&s.env, &[8; 32]);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &other, groth16_seal(&s.env)));
+    assert_fog_of_chess_error(&result, Error::InvalidProof);
+
+    let short_seal = Bytes::from_array(&s.env, &GROTH16_SELECTOR);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, short_seal));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+
+    let unknown_selector = Bytes::from_array(&s.env, &[0u8; GROTH16_SEAL_LEN as usize]);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, unknown_selector));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+
+    assert!(s.verifier.verified().is_empty());
+}
+
+#[test]
+fn test_dev_seal_needs_dev_verifier() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let journal_sha256: BytesN<32> = s.env.crypto().sha256(&e4_journal(&s.env)).into();
+    let proof = move_proof(&s.env, &commitment, dev_seal(&s.env, &journal_sha256));
+    let result = s.client.try_verify_move(&s.player1, &1u32, &proof);
+    assert_fog_of_chess_error(&result, Error::DevProofRejected);
+
+    s.client.set_dev_verifier(&true);
+    assert!(s.client.verify_move(&s.player1, &1u32, &proof));
+
+    // A dev seal only vouches for the digest it carries
+    let wrong_digest = dev_seal(&s.env, &BytesN::from_array(&s.env, &[6; 32]));
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, wrong_digest));
+    assert_fog_of_chess_error(&result, Error::InvalidProof);
+
+    // Dev seals never reach the real verifier
+    assert!(s.verifier.verified().is_empty());
+}
+
+#[test]
+fn test_verify_moves_counts_batch() {
+    let s = setup_test();
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &[9; 32]));
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let proof = batch_proof(&s.env, 0, &[4, 4, 3, 4, 2, 4]);
+    let journal_sha256: BytesN<32> = s.env.crypto().sha256(&proof.journal).into();
+
+    assert_eq!(s.client.verify_moves(&s.player1, &1u32, &proof), 3);
+    assert_eq!(s.verifier.verified(), vec![&s.env, (journal_sha256, image_id(&s.env, 2))]);
+    assert_eq!(s.client.get_ply_count(&1u32, &s.player1), 3);
+
+    // Moves from any other circuit are refused
+    let mut journal = Bytes::from(image_id(&s.env, 5));
+    journal.extend_from_slice(&[0, 4, 4]);
+    let result = s.client.try_verify_moves(&s.player1, &1u32, &BatchProof { seal: groth16_seal(&s.env), journal });
+    assert_fog_of_chess_error(&result, Error::InvalidProof);
+}
+
+#[test]
+fn test_verify_moves_checks_player_color() {
+    let s = setup_test();
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &[9; 32]));
+    s.client.commit_board(&s.player2, &BytesN::from_array(&s.env, &[8; 32]));
+
+    // No game yet, so no side to check against
+    let result = s.client.try_verify_moves(&s.player2, &1u32, &batch_proof(&s.env, 1, &[2, 4]));
+    assert_fog_of_chess_error(&result, Error::SessionNotFound);
+
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    // Proving a black pawn's move as white would flip its direction
+    let result = s.client.try_verify_moves(&s.player2, &1u32, &batch_proof(&s.env, 0, &[2, 4]));
+    assert_fog_of_chess_error(&result, Error::WrongColor);
+    let result = s.client.try_verify_moves(&s.player1, &1u32, &batch_proof(&s.env, 1, &[5, 4]));
+    assert_fog_of_chess_error(&result, Error::WrongColor);
+    let result = s.client.try_verify_moves(&s.player1, &1u32, &batch_proof(&s.env, 2, &[5, 4]));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+    assert!(s.verifier.verified().is_empty());
+
+    assert_eq!(s.client.verify_moves(&s.player2, &1u32, &batch_proof(&s.env, 1, &[2, 4])), 1);
+    assert_eq!(s.client.verify_moves(&s.player1, &1u32, &batch_proof(&s.env, 0, &[5, 4])), 1);
+}
+
+#[test]
+fn test_verify_move_checks_journal_color() {
+    let s = setup_test();
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &[9; 32]));
+    let commitment = BytesN::from_array(&s.env, &[8; 32]);
+    s.client.commit_board(&s.player2, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    // player2 plays black, so white's e4 isn't theirs to prove
+    let result = s.client.try_verify_move(&s.player2, &1u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env)));
+    assert_fog_of_chess_error(&result, Error::WrongColor);
+
+    // Journals the move circuit can't have committed
+    let e5 = encode_move_journal(Square::from_pos([3, 4]).unwrap(), Color::Black);
+    let mut off_board = e5;
+    off_board[4] = 8;
+    let mut no_color = e5;
+    no_color[8] = 2;
+    let mut padded = e5;
+    padded[9] = 1;
+    let mut long = e5.to_vec();
+    long.push(0);
+    for journal in [&off_board[..], &no_color[..], &padded[..], &long[..]] {
+        let mut proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+        proof.journal = Bytes::from_slice(&s.env, journal);
+        let result = s.client.try_verify_move(&s.player2, &1u32, &proof);
+        assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+    }
+    assert!(s.verifier.verified().is_empty());
+
+    // The seal is checked against the digest of the journal as sent
+    let mut proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+    proof.journal = Bytes::from_array(&s.env, &e5);
+    assert!(s.client.verify_move(&s.player2, &1u32, &proof));
+    let e5_sha256: BytesN<32> = s.env.crypto().sha256(&proof.journal).into();
+    assert_eq!(s.verifier.verified(), vec![&s.env, (e5_sha256, image_id(&s.env, 1))]);
+
+    // A second game with the sides swapped doesn't change this game's colors
+    s.client.start_game(&2u32, &s.player2, &s.player1);
+    assert!(s.client.verify_move(&s.player2, &2u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env))));
+    let result = s.client.try_verify_move(&s.player2, &1u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env)));
+    assert_fog_of_chess_error(&result, Error::WrongColor);
+}
+
+#[test]
+fn test_move_seal_checked_against_stored_image_id() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    // The image id a proof claims is ignored; only the stored METHOD_ID is verified
+    let mut proof = move_proof(&s.env, &commitment, groth16_seal(&s.env));
+    proof.image_id = image_id(&s.env, 9);
+    s.client.verify_move(&s.player1, &1u32, &proof);
+    assert_eq!(s.verifier.verified().get(0).unwrap().1, image_id(&s.env, 1));
+}
+
+#[test]
+fn test_seal_length_must_match_selector() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+    s.client.set_dev_verifier(&true);
+
+    let mut long_groth16 = groth16_seal(&s.env);
+    long_groth16.push_back(0);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, long_groth16));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+
+    let mut long_dev = dev_seal(&s.env, &BytesN::from_array(&s.env, &[7; 32]));
+    long_dev.push_back(0);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, long_dev));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, Bytes::new(&s.env)));
+    assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+}
+
+#[test]
+fn test_no_moves_once_the_game_is_over() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+    s.client.verify_moves(&s.player1, &1u32, &batch_proof(&s.env, 0, &[4, 4]));
+    agree_result(&s, 1, true);
+
+    // Proofs sent after the game can't pad the ply count or the move chain
+    let chain = s.client.get_move_chain(&1u32, &s.player1);
+    let result = s.client.try_verify_move(&s.player1, &1u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env)));
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    let result = s.client.try_verify_moves(&s.player1, &1u32, &batch_proof(&s.env, 0, &[3, 4]));
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    assert_eq!(s.client.get_ply_count(&1u32, &s.player1), 1);
+    assert_eq!(s.client.get_move_chain(&1u32, &s.player1), chain);
+
+    // Nor can moves land between games
+    let result = s.client.try_verify_moves(&s.player1, &2u32, &batch_proof(&s.env, 0, &[3, 4]));
+    assert_fog_of_chess_error(&result, Error::SessionNotFound);
+}
+
+#[test]
+fn test_verify_moves_rejects_malformed_journal() {
+    let s = setup_test();
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &[9; 32]));
+    s.client.start_game(&1u32, &s.player1, &s.player2);
+
+    let batch = |end_positions: &[u8]| batch_proof(&s.env, 0, end_positions);
+
+    // No moves, half a move, a square off the board, one move too many
+    for end_positions in [&[][..], &[4, 4, 3][..], &[4, 8][..], &[4u8; 2 * 33][..]] {
+        let result = s.client.try_verify_moves(&s.player1, &1u32, &batch(end_positions));
+        assert_fog_of_chess_error(&result, Error::InvalidProofFormat);
+    }
+
+    // 32 moves is the most one batch carries
+    assert_eq!(s.client.verify_moves(&s.player1, &1u32, &batch(&[4u8; 2 * 32])), 32);
+    assert_eq!(s.verifier.verified().len(), 1);
+}
+
+#[test]
+fn test_verify_moves_needs_batch_image_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = FogOfChessContractClient::new(&env, &env.register(FogOfChessContract, ()));
+    let hub = Address::generate(&env);
+    client.init(&Address::generate(&env), &hub, &image_id(&env, 1));
+
+    let player = Address::generate(&env);
+    client.commit_board(&player, &BytesN::from_array(&env, &[9; 32]));
+    let result = client.try_verify_moves(&player, &1u32, &batch_proof(&env, 0, &[4, 4]));
+    assert_fog_of_chess_error(&result, Error::NotInitialized);
+}
+
+#[test]
+fn test_default_verifier_is_nethermind() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = FogOfChessContractClient::new(&env, &env.register(FogOfChessContract, ()));
+
+    let nethermind = Address::from_string(&String::from_str(&env, NETHERMIND_VERIFIER_ID));
+    assert_eq!(client.get_verifier(), nethermind);
+
+    let result = client.try_set_verifier(&Address::generate(&env));
+    assert_fog_of_chess_error(&result, Error::NotInitialized);
+}
+
+// ============================================================================
+// Replay Tests
+// ============================================================================
+
+#[test]
+fn test_finalize_game_reports_replayed_winner() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&10u32, &s.player1, &s.player2);
+    play(&s, 10, WHITE_ENDS, BLACK_ENDS);
+
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins));
+    assert!(s.client.finalize_game(&s.player2, &10u32, &proof));
+    assert!(s.client.is_finalized(&10u32));
+    assert_eq!(s.hub.calls_for(&10u32).get(1), Some(HubCall::EndGame(10, true)));
+
+    // A proven result is final
+    let result = s.client.try_end_game(&s.player2, &10u32, &false);
+    assert_fog_of_chess_error(&result, Error::AlreadyFinalized);
+    let result = s.client.try_finalize_game(&s.player1, &10u32, &proof);
+    assert_fog_of_chess_error(&result, Error::AlreadyFinalized);
+}
+
+#[test]
+fn test_finalize_game_refused_once_hub_has_paid_out() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&11u32, &s.player1, &s.player2);
+    play(&s, 11, WHITE_ENDS, BLACK_ENDS);
+    agree_result(&s, 11, true);
+
+    // The hub has settled the session and can't settle it again, so a replay
+    // can't change the result on either side
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::BlackWins));
+    let result = s.client.try_finalize_game(&s.player2, &11u32, &proof);
+    assert_fog_of_chess_error(&result, Error::GameOver);
+    assert!(s.client.get_session(&11u32).unwrap().player1_won);
+    assert!(!s.client.is_finalized(&11u32));
+    assert_eq!(s.hub.calls_for(&11u32).len(), 2);
+}
+
+#[test]
+fn test_replay_overturns_false_claim() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&19u32, &s.player1, &s.player2);
+    play(&s, 19, WHITE_ENDS, BLACK_ENDS);
+
+    // Black claims a win white took, and white disputes it
+    s.client.end_game(&s.player2, &19u32, &false);
+    s.client.end_game(&s.player1, &19u32, &true);
+    assert_eq!(s.hub.calls_for(&19u32).len(), 1);
+
+    // The replay decides, and the claim can't be settled afterwards
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins));
+    assert!(s.client.finalize_game(&s.player1, &19u32, &proof));
+    assert_eq!(s.hub.calls_for(&19u32).get(1), Some(HubCall::EndGame(19, true)));
+    assert!(s.client.get_session(&19u32).unwrap().player1_won);
+
+    pass_challenge_window(&s);
+    let result = s.client.try_settle_game(&19u32);
+    assert_fog_of_chess_error(&result, Error::AlreadyFinalized);
+    assert_eq!(s.hub.calls_for(&19u32).len(), 2);
+}
+
+#[test]
+fn test_undecided_replay_settles_nothing() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&12u32, &s.player1, &s.player2);
+    play(&s, 12, WHITE_ENDS, BLACK_ENDS);
+
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::Undecided));
+    let result = s.client.try_finalize_game(&s.player1, &12u32, &proof);
+    assert_fog_of_chess_error(&result, Error::GameUndecided);
+    assert!(s.client.get_session(&12u32).unwrap().active);
+    assert_eq!(s.hub.calls_for(&12u32).len(), 1);
+}
+
+#[test]
+fn test_replay_must_start_from_committed_boards() {
+    let s = setup_test();
+    s.client.commit_board(&s.player1, &BytesN::from_array(&s.env, &[1; 32]));
+    s.client.commit_board(&s.player2, &BytesN::from_array(&s.env, &[2; 32]));
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&13u32, &s.player1, &s.player2);
+    play(&s, 13, WHITE_ENDS, BLACK_ENDS);
+
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins));
+    let result = s.client.try_finalize_game(&s.player1, &13u32, &proof);
+    assert_fog_of_chess_error(&result, Error::InvalidProof);
+}
+
+#[test]
+fn test_replay_must_be_the_game_played() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    s.client.start_game(&14u32, &s.player1, &s.player2);
+    play(&s, 14, WHITE_ENDS, BLACK_ENDS);
+
+    // An invented game: white's queen goes somewhere else
+    let invented = replay_proof(&s.env, &replay_journal(&[4, 4, 1, 5], BLACK_ENDS, GameResult::WhiteWins));
+    let result = s.client.try_finalize_game(&s.player1, &14u32, &invented);
+    assert_fog_of_chess_error(&result, Error::ReplayMismatch);
+
+    // The moves played, but sides swapped
+    let swapped = replay_proof(&s.env, &replay_journal(BLACK_ENDS, WHITE_ENDS, GameResult::WhiteWins));
+    let result = s.client.try_finalize_game(&s.player1, &14u32, &swapped);
+    assert_fog_of_chess_error(&result, Error::ReplayMismatch);
+
+    // The moves played, but a different ply count
+    let mut journal = replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins);
+    journal.plies += 1;
+    let result = s.client.try_finalize_game(&s.player1, &14u32, &replay_proof(&s.env, &journal));
+    assert_fog_of_chess_error(&result, Error::ReplayMismatch);
+
+    // A game that stops before the last verified move
+    let short = replay_proof(&s.env, &replay_journal(&WHITE_ENDS[..2], BLACK_ENDS, GameResult::BlackWins));
+    let result = s.client.try_finalize_game(&s.player2, &14u32, &short);
+    assert_fog_of_chess_error(&result, Error::ReplayMismatch);
+
+    assert!(s.client.get_session(&14u32).unwrap().active);
+    assert_eq!(s.hub.calls_for(&14u32).len(), 1);
+}
+
+#[test]
+fn test_each_game_replays_its_own_moves() {
+    let s = setup_test();
+    commit_initial_boards(&s);
+    s.client.set_dev_verifier(&true);
+    let proof = replay_proof(&s.env, &replay_journal(WHITE_ENDS, BLACK_ENDS, GameResult::WhiteWins));
+
+    s.client.start_game(&16u32, &s.player1, &s.player2);
+    play(&s, 16, WHITE_ENDS, BLACK_ENDS);
+    s.client.finalize_game(&s.player1, &16u32, &proof);
+
+    // The next game between the same players starts from an empty chain
+    s.client.start_game(&17u32, &s.player1, &s.player2);
+    assert_eq!(s.client.get_ply_count(&17u32, &s.player1), 0);
+    play(&s, 17, WHITE_ENDS, BLACK_ENDS);
+    assert!(s.client.finalize_game(&s.player2, &17u32, &proof));
+    assert_eq!(s.hub.calls_for(&17u32).get(1), Some(HubCall::EndGame(17, true)));
+
+    // Only the session's players move in it
+    let outsider = Address::generate(&s.env);
+    s.client.commit_board(&outsider, &BytesN::from_array(&s.env, &[9; 32]));
+    s.client.start_game(&18u32, &s.player1, &s.player2);
+    let result = s.client.try_verify_moves(&outsider, &18u32, &batch_proof(&s.env, 0, &[4, 4]));
+    assert_fog_of_chess_error(&result, Error::NotAuthorized);
+}
+
+#[test]
+fn test_move_chain_covers_single_and_batched_moves() {
+    let s = setup_test();
+    let commitment = BytesN::from_array(&s.env, &[9; 32]);
+    s.client.commit_board(&s.player1, &commitment);
+    s.client.start_game(&15u32, &s.player1, &s.player2);
+    assert_eq!(s.client.get_move_chain(&15u32, &s.player1), BytesN::from_array(&s.env, &[0; 32]));
+
+    // A single move chains the journal digest its seal was checked against...
+    s.client.verify_move(&s.player1, &15u32, &move_proof(&s.env, &commitment, groth16_seal(&s.env)));
+    let e4_sha256: BytesN<32> = s.env.crypto().sha256(&e4_journal(&s.env)).into();
+    let chain = extend_move_chain(&[0; 32], &e4_sha256.to_array());
+    assert_eq!(s.client.get_move_chain(&15u32, &s.player1), BytesN::from_array(&s.env, &chain));
+
+    // ...and a batch chains each move's own journal, the same as one at a time
+    s.client.verify_moves(&s.player1, &15u32, &batch_proof(&s.env, 0, &[4, 4]));
+    let chain = extend_move_chain(&chain, &e4_sha256.to_array());
+    assert_eq!(s.client.get_move_chain(&15u32, &s.player1), BytesN::from_array(&s.env, &chain));
+    assert_eq!(s.client.get_move_chain(&15u32, &s.player2), BytesN::from_array(&s.env, &[0; 32]));
+}
+
+// ============================================================================
+// Audit Tests
+// ============================================================================
+
+#[test]
+fn test_reveal_board_checks_commitment() {
+    let s = setup_test();
+    let (white, black) = commit_initial_boards(&s);
+    s.client.start_game(&20u32, &s.player1, &s.player2);
+
+    let result = s.client.try_reveal_board(&s.player1, &20u32, &white, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::GameNotOver);
+
+    agree_result(&s, 20, true);
+
+    let result = s.client.try_reveal_board(&s.player1, &20u32, &white, &BLACK_SALT);
+    assert_fog_of_chess_error(&result, Error::CommitmentMismatch);
+    let result = s.client.try_reveal_board(&s.player1, &20u32, &Bytes::from_array(&s.env, &[7u8; 64]), &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::InvalidBoard);
+
+    s.client.reveal_board(&s.player1, &20u32, &white, &WHITE_SALT);
+    s.client.reveal_board(&s.player2, &20u32, &black, &BLACK_SALT);
+    let result = s.client.try_reveal_board(&s.player1, &20u32, &white, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::AlreadyRevealed);
+
+    let audit = s.client.get_audit(&20u32).unwrap();
+    assert_eq!(audit.player1_board, Some(white));
+    assert_eq!(audit.player2_board, Some(black));
+}
+
+#[test]
+fn test_flag_mismatch_needs_revealed_board() {
+    let s = setup_test();
+    let (white, _black) = commit_initial_boards(&s);
+    s.client.start_game(&21u32, &s.player1, &s.player2);
+    agree_result(&s, 21, false);
+
+    let result = s.client.try_flag_mismatch(&s.player2, &21u32, &60u32);
+    assert_fog_of_chess_error(&result, Error::NotRevealed);
+
+    s.client.reveal_board(&s.player1, &21u32, &white, &WHITE_SALT);
+    s.client.flag_mismatch(&s.player2, &21u32, &60u32);
+    let result = s.client.try_flag_mismatch(&s.player2, &21u32, &60u32);
+    assert_fog_of_chess_error(&result, Error::AlreadyFlagged);
+    let result = s.client.try_flag_mismatch(&s.player2, &21u32, &64u32);
+    assert_fog_of_chess_error(&result, Error::InvalidBoard);
+
+    let flags = s.client.get_audit(&21u32).unwrap().flags;
+    assert_eq!(flags.len(), 1);
+    let flag = flags.get(0).unwrap();
+    assert_eq!(flag.flagged_by, s.player2);
+    assert_eq!(flag.square, 60);
+    assert_eq!(flag.ledger, 100);
+}
+
+#[test]
+fn test_reveal_board_rejects_outsiders_and_malformed_boards() {
+    let s = setup_test();
+    let (white, black) = commit_initial_boards(&s);
+
+    let result = s.client.try_reveal_board(&s.player1, &22u32, &white, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::SessionNotFound);
+
+    s.client.start_game(&22u32, &s.player1, &s.player2);
+    agree_result(&s, 22, true);
+
+    let outsider = Address::generate(&s.env);
+    let result = s.client.try_reveal_board(&outsider, &22u32, &white, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::NotAuthorized);
+
+    let result = s.client.try_reveal_board(&s.player1, &22u32, &white.slice(0..63), &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::InvalidBoard);
+    let mut long = white.clone();
+    long.push_back(0);
+    let result = s.client.try_reveal_board(&s.player1, &22u32, &long, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::InvalidBoard);
+
+    // Revealing the opponent's setup, even with their salt, is a mismatch
+    let result = s.client.try_reveal_board(&s.player1, &22u32, &black, &BLACK_SALT);
+    assert_fog_of_chess_error(&result, Error::CommitmentMismatch);
+    let mut tampered = white.clone();
+    tampered.set(48, 0);
+    let result = s.client.try_reveal_board(&s.player1, &22u32, &tampered, &WHITE_SALT);
+    assert_fog_of_chess_error(&result, Error::CommitmentMismatch);
+
+    assert!(s.client.get_audit(&22u32).is_none());
+}
+
+#[test]
+fn test_flag_mismatch_rejects_outsiders() {
+    let s = setup_test();
+    let (white, _black) = commit_initial_boards(&s);
+
+    let result = s.client.try_flag_mismatch(&s.player2, &23u32, &60u32);
+    assert_fog_of_chess_error(&result, Error::SessionNotFound);
+
+    s.client.start_game(&23u32, &s.player1, &s.player2);
+    agree_result(&s, 23, true);
+    s.client.reveal_board(&s.player1, &23u32, &white, &WHITE_SALT);
+
+    let outsider = Address::generate(&s.env);
+    let result = s.client.try_flag_mismatch(&outsider, &23u32, &60u32);
+    assert_fog_of_chess_error(&result, Error::NotAuthorized);
+
+    // Player 1 can't flag their own revealed board, only the opponent's
+    let result = s.client.try_flag_mismatch(&s.player1, &23u32, &60u32);
+    assert_fog_of_chess_error(&result, Error::NotRevealed);
+
+    assert!(s.client.get_audit(&23u32).unwrap().flags.is_empty());
+}
@@ -0,0 +1,71 @@
+// Integration tests against built wasm instead of test doubles. They need
+//   target/wasm32v1-none/release/mock_game_hub.wasm    (`bun run build mock-game-hub`)
+//   target/wasm32v1-none/release/groth16_verifier.wasm (built from
+//     https://github.com/NethermindEth/stellar-risc0-verifier and copied there)
+// and run with `cargo test -p board-commitment-contract --features wasm-import`.
+
+use crate::{FogOfChessContract, FogOfChessContractClient, ZKProof, GROTH16_SELECTOR, GROTH16_SEAL_LEN};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env};
+
+mod mock_game_hub {
+    use soroban_sdk::contractimport;
+    contractimport!(file = "../../target/wasm32v1-none/release/mock_game_hub.wasm");
+}
+
+mod groth16_verifier {
+    use soroban_sdk::contractimport;
+    contractimport!(file = "../../target/wasm32v1-none/release/groth16_verifier.wasm");
+}
+
+fn setup_test() -> (Env, FogOfChessContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let hub_addr = env.register(mock_game_hub::WASM, ());
+    let verifier_addr = env.register(groth16_verifier::WASM, ());
+
+    let admin = Address::generate(&env);
+    let client = FogOfChessContractClient::new(&env, &env.register(FogOfChessContract, ()));
+    client.init(&admin, &hub_addr, &BytesN::from_array(&env, &[1; 32]));
+    client.set_verifier(&verifier_addr);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    (env, client, player1, player2)
+}
+
+#[test]
+fn test_session_through_mock_hub_wasm() {
+    let (_env, client, player1, player2) = setup_test();
+
+    client.start_game(&1u32, &player1, &player2);
+    client.extend_deadline(&player2, &1u32, &500);
+    client.end_game(&player1, &1u32, &true);
+    client.end_game(&player2, &1u32, &true);
+
+    let session = client.get_session(&1u32).unwrap();
+    assert!(!session.active);
+    assert!(session.player1_won);
+}
+
+#[test]
+fn test_real_verifier_rejects_forged_seal() {
+    let (env, client, player1, player2) = setup_test();
+    let commitment = BytesN::from_array(&env, &[9; 32]);
+    client.commit_board(&player1, &commitment);
+    client.start_game(&1u32, &player1, &player2);
+
+    // Right selector and length, but no actual proof behind it
+    let mut seal = Bytes::from_array(&env, &GROTH16_SELECTOR);
+    seal.extend_from_slice(&[0u8; (GROTH16_SEAL_LEN - 4) as usize]);
+    let proof = ZKProof {
+        seal,
+        journal: Bytes::from_array(&env, &[4, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0]), // white to e4
+        image_id: BytesN::from_array(&env, &[1; 32]),
+        public_inputs: vec![&env, commitment],
+    };
+
+    assert!(client.try_verify_move(&player1, &1u32, &proof).is_err());
+    assert_eq!(client.get_ply_count(&1u32, &player1), 0);
+}
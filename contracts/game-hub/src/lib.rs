@@ -0,0 +1,832 @@
+#![no_std]
+
+//! # Game Hub
+//!
+//! The contract every game reports to. Games open a session with `start_game`,
+//! which locks both players' points in escrow, and close it with `end_game`,
+//! which pays the whole pot to the winner and updates both players' standings.
+//! `end_game_with_outcome` also covers draws (stakes returned) and
+//! cancellations (stakes refunded, no result recorded).
+//!
+//! Points are hub-internal balances, not a token: the admin credits them, and
+//! they only move between players through finished games.
+//!
+//! Every session is kept with its players, stakes and result. Session ids are
+//! unique across the hub, so no game can reuse one.
+//!
+//! Only games the admin has registered can open sessions. A suspended game
+//! can still end the sessions it already started, so no points get stuck.
+//!
+//! Each decided or drawn session updates both players' Elo ratings, overall
+//! and in that game; see `rating` for the arithmetic.
+//!
+//! Every session has a deadline. Games push it back as play goes on with
+//! `extend_deadline`; once it has passed, anyone can call `expire_session` to
+//! release the escrow under the game's expiry rule.
+//!
+//! Same `start_game` / `end_game` interface as `mock-game-hub`, so games can
+//! switch hubs with `set_hub` and no code changes. The interface, `Outcome`,
+//! `Session` and the session events are defined once in `game-hub-client`.
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, Address, BytesN, Env, String, Vec,
+};
+
+use game_hub_client::{DeadlineExtended, GameEnded, GameSettled, GameStarted};
+pub use game_hub_client::{Outcome, Session, SessionStatus};
+
+mod rating;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    SessionNotFound = 1,
+    SessionExists = 2,
+    SessionEnded = 3,
+    InsufficientPoints = 4,
+    InvalidPoints = 5,
+    SelfPlay = 6,
+    GameNotRegistered = 7,
+    GameAlreadyRegistered = 8,
+    GameSuspended = 9,
+    SessionNotExpired = 10,
+    InvalidDeadline = 11,
+    NotAPlayer = 12,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct SessionExpired {
+    pub session_id: u32,
+    pub winner: Option<Address>, // set when the expiry rule forfeited the session
+}
+
+#[contractevent]
+pub struct RatingChanged {
+    pub player: Address,
+    pub session_id: u32,
+    pub game_id: Address,
+    pub game_rating: i32,
+    pub overall_rating: i32,
+}
+
+#[contractevent]
+pub struct GameRegistered {
+    pub game_id: Address,
+    pub name: String,
+    pub version: u32,
+}
+
+#[contractevent]
+pub struct GameStatusChanged {
+    pub game_id: Address,
+    pub suspended: bool,
+}
+
+#[contractevent]
+pub struct PointsCredited {
+    pub player: Address,
+    pub amount: i128,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// What `expire_session` does with a session that ran past its deadline.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExpiryRule {
+    Refund,  // both stakes go back, no result recorded
+    Forfeit, // the player the game was waiting on loses; refund if it never said
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameInfo {
+    pub name: String,
+    pub version: u32,
+    pub registered_ledger: u32,
+    pub suspended: bool,          // no new sessions while set
+    pub session_ledgers: u32,     // deadline of a new session, in ledgers after it starts
+    pub expiry_rule: ExpiryRule,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Standing {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub points_won: i128,  // opponents' stakes collected
+    pub points_lost: i128, // own stakes lost
+}
+
+/// A player's Elo rating, overall or in one game.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rating {
+    pub rating: i32,
+    pub games: u32,        // rated games behind this rating
+    pub provisional: bool, // fewer than 20 rated games; moves twice as fast
+}
+
+/// One entry in a player's rating history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingChange {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub ledger: u32,
+    pub game_rating: i32, // rating in `game_id` after this session
+    pub game_delta: i32,
+    pub overall_rating: i32,
+    pub overall_delta: i32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Game(Address),
+    Balance(Address),
+    Standing(Address),
+    Session(u32),
+    ActiveSessions(Address), // Vec<u32> of the player's active session ids
+    History(Address),        // Vec<u32> of the player's ended session ids, newest first
+    Rating(Address),
+    GameRating(Address, Address), // (game_id, player)
+    RatingHistory(Address),       // Vec<RatingChange>, newest first
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Balances, standings and sessions live in persistent storage and are bumped
+// on every write, so a quiet player doesn't lose their points.
+
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const PERSISTENT_TTL_LEDGERS: u32 = 518_400;
+/// Bump once less than a day (17,280 ledgers) is left
+const PERSISTENT_TTL_THRESHOLD: u32 = 17_280;
+
+/// Ended sessions (and rating changes) kept in each player's history; older
+/// ones drop off the list (the sessions themselves stay readable through get_session)
+const MAX_HISTORY: u32 = 50;
+
+/// Sessions a game hasn't configured expire after 7 days = 120,960 ledgers
+const DEFAULT_SESSION_LEDGERS: u32 = 120_960;
+/// Deadlines stay within the persistent TTL, which every session write renews,
+/// so a session can't be archived before it is allowed to expire
+const MAX_SESSION_LEDGERS: u32 = PERSISTENT_TTL_LEDGERS;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn read_persistent<T: soroban_sdk::TryFromVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey) -> Option<T> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+    }
+    value
+}
+
+fn write_persistent<T: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &T) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+}
+
+fn balance_of(env: &Env, player: &Address) -> i128 {
+    read_persistent(env, &DataKey::Balance(player.clone())).unwrap_or(0)
+}
+
+fn set_balance(env: &Env, player: &Address, balance: i128) {
+    write_persistent(env, &DataKey::Balance(player.clone()), &balance);
+}
+
+/// Move `amount` from the player's balance into escrow.
+fn lock_points(env: &Env, player: &Address, amount: i128) -> Result<(), Error> {
+    let balance = balance_of(env, player);
+    if balance < amount {
+        return Err(Error::InsufficientPoints);
+    }
+    set_balance(env, player, balance - amount);
+    Ok(())
+}
+
+fn standing_of(env: &Env, player: &Address) -> Standing {
+    read_persistent(env, &DataKey::Standing(player.clone())).unwrap_or_default()
+}
+
+fn load_session(env: &Env, session_id: u32) -> Result<Session, Error> {
+    read_persistent(env, &DataKey::Session(session_id)).ok_or(Error::SessionNotFound)
+}
+
+/// An active session can end, be cancelled or expire, once; all three are final.
+fn transition(session: &mut Session, to: SessionStatus, ledger: u32) -> Result<(), Error> {
+    match (session.status, to) {
+        (SessionStatus::Active, SessionStatus::Ended | SessionStatus::Cancelled | SessionStatus::Expired) => {
+            session.status = to;
+            session.end_ledger = Some(ledger);
+            Ok(())
+        }
+        (SessionStatus::Ended | SessionStatus::Cancelled | SessionStatus::Expired, _) => Err(Error::SessionEnded),
+        (SessionStatus::Active, SessionStatus::Active) => Err(Error::SessionExists),
+    }
+}
+
+fn update_standing(env: &Env, player: &Address, update: impl FnOnce(&mut Standing)) {
+    let mut standing = standing_of(env, player);
+    update(&mut standing);
+    write_persistent(env, &DataKey::Standing(player.clone()), &standing);
+}
+
+fn rating_of(env: &Env, key: &DataKey) -> Rating {
+    read_persistent(env, key).unwrap_or(Rating {
+        rating: rating::INITIAL_RATING,
+        games: 0,
+        provisional: true,
+    })
+}
+
+/// Apply one result to a pair of ratings, both moved from their values before
+/// the game. `score1` is the first side's score. Returns each side's new
+/// rating and change.
+fn rate_pair(env: &Env, keys: [DataKey; 2], score1: i32) -> [(i32, i32); 2] {
+    let (a, b) = (rating_of(env, &keys[0]), rating_of(env, &keys[1]));
+    let deltas = [
+        rating::rating_delta(a.rating, b.rating, a.games, score1),
+        rating::rating_delta(b.rating, a.rating, b.games, rating::WIN - score1),
+    ];
+    let mut result = [(0, 0); 2];
+    for (i, (key, mut current)) in keys.iter().zip([a, b]).enumerate() {
+        current.rating += deltas[i];
+        current.games += 1;
+        current.provisional = current.games < rating::PROVISIONAL_GAMES;
+        write_persistent(env, key, &current);
+        result[i] = (current.rating, deltas[i]);
+    }
+    result
+}
+
+/// Update both players' overall and per-game ratings from a decided or drawn
+/// session. `score1` is player1's score, per mille.
+fn update_ratings(env: &Env, session: &Session, score1: i32) {
+    let players = [session.player1.clone(), session.player2.clone()];
+    let game = rate_pair(
+        env,
+        players.clone().map(|player| DataKey::GameRating(session.game_id.clone(), player)),
+        score1,
+    );
+    let overall = rate_pair(env, players.clone().map(DataKey::Rating), score1);
+
+    for (i, player) in players.into_iter().enumerate() {
+        let change = RatingChange {
+            session_id: session.session_id,
+            game_id: session.game_id.clone(),
+            ledger: env.ledger().sequence(),
+            game_rating: game[i].0,
+            game_delta: game[i].1,
+            overall_rating: overall[i].0,
+            overall_delta: overall[i].1,
+        };
+        let key = DataKey::RatingHistory(player.clone());
+        let mut history: Vec<RatingChange> = read_persistent(env, &key).unwrap_or_else(|| Vec::new(env));
+        history.push_front(change);
+        while history.len() > MAX_HISTORY {
+            history.pop_back();
+        }
+        write_persistent(env, &key, &history);
+
+        RatingChanged {
+            player,
+            session_id: session.session_id,
+            game_id: session.game_id.clone(),
+            game_rating: game[i].0,
+            overall_rating: overall[i].0,
+        }
+        .publish(env);
+    }
+}
+
+/// Release a session's escrow according to `outcome` and record the result.
+fn pay_out(env: &Env, session: &mut Session, outcome: Outcome) {
+    let (player1, player2) = (&session.player1, &session.player2);
+    let (stake1, stake2) = (session.player1_points, session.player2_points);
+    match outcome {
+        Outcome::Player1Won | Outcome::Player2Won => {
+            let (winner, loser, loser_stake) = if outcome == Outcome::Player1Won {
+                (player1, player2, stake2)
+            } else {
+                (player2, player1, stake1)
+            };
+            // The winner takes back their own stake plus the loser's
+            set_balance(env, winner, balance_of(env, winner) + stake1 + stake2);
+            update_standing(env, winner, |s| {
+                s.wins += 1;
+                s.points_won += loser_stake;
+            });
+            update_standing(env, loser, |s| {
+                s.losses += 1;
+                s.points_lost += loser_stake;
+            });
+            let score1 = if outcome == Outcome::Player1Won { rating::WIN } else { rating::LOSS };
+            update_ratings(env, session, score1);
+            session.winner = Some(winner.clone());
+        }
+        Outcome::Draw | Outcome::Cancelled => {
+            set_balance(env, player1, balance_of(env, player1) + stake1);
+            set_balance(env, player2, balance_of(env, player2) + stake2);
+            if outcome == Outcome::Draw {
+                update_standing(env, player1, |s| s.draws += 1);
+                update_standing(env, player2, |s| s.draws += 1);
+                update_ratings(env, session, rating::DRAW);
+            }
+        }
+    }
+}
+
+/// Store a closed session and move it into both players' history.
+fn close(env: &Env, session: &Session) {
+    write_persistent(env, &DataKey::Session(session.session_id), session);
+    archive(env, &session.player1, session.session_id);
+    archive(env, &session.player2, session.session_id);
+}
+
+/// Settle a session with the game's reported outcome.
+/// Only the game that opened the session may settle it.
+fn settle(env: &Env, session_id: u32, outcome: Outcome) -> Result<(), Error> {
+    let mut session = load_session(env, session_id)?;
+    session.game_id.require_auth();
+
+    let status = if outcome == Outcome::Cancelled { SessionStatus::Cancelled } else { SessionStatus::Ended };
+    transition(&mut session, status, env.ledger().sequence())?;
+    pay_out(env, &mut session, outcome);
+    close(env, &session);
+
+    match outcome {
+        Outcome::Player1Won | Outcome::Player2Won => GameEnded {
+            session_id,
+            player1_won: outcome == Outcome::Player1Won,
+        }
+        .publish(env),
+        Outcome::Draw | Outcome::Cancelled => GameSettled { session_id, outcome }.publish(env),
+    }
+    Ok(())
+}
+
+/// How an expired session settles: a refund, or a loss for the player the
+/// game was waiting on when the rule is `Forfeit`.
+fn expiry_outcome(session: &Session, rule: ExpiryRule) -> Outcome {
+    match (rule, &session.waiting_on) {
+        (ExpiryRule::Forfeit, Some(player)) if *player == session.player1 => Outcome::Player2Won,
+        (ExpiryRule::Forfeit, Some(_)) => Outcome::Player1Won,
+        _ => Outcome::Cancelled,
+    }
+}
+
+fn session_ids(env: &Env, key: &DataKey) -> Vec<u32> {
+    read_persistent(env, key).unwrap_or_else(|| Vec::new(env))
+}
+
+fn load_sessions(env: &Env, ids: Vec<u32>) -> Vec<Session> {
+    let mut sessions = Vec::new(env);
+    for id in ids.iter() {
+        if let Ok(session) = load_session(env, id) {
+            sessions.push_back(session);
+        }
+    }
+    sessions
+}
+
+fn add_active(env: &Env, player: &Address, session_id: u32) {
+    let key = DataKey::ActiveSessions(player.clone());
+    let mut ids = session_ids(env, &key);
+    ids.push_back(session_id);
+    write_persistent(env, &key, &ids);
+}
+
+/// Move a session from the player's active list to the front of their history.
+/// Cancelled sessions are listed too, so a player can see every game they joined.
+fn archive(env: &Env, player: &Address, session_id: u32) {
+    let key = DataKey::ActiveSessions(player.clone());
+    let mut ids = session_ids(env, &key);
+    if let Some(index) = ids.first_index_of(session_id) {
+        ids.remove(index);
+    }
+    write_persistent(env, &key, &ids);
+
+    let key = DataKey::History(player.clone());
+    let mut history = session_ids(env, &key);
+    history.push_front(session_id);
+    while history.len() > MAX_HISTORY {
+        history.pop_back();
+    }
+    write_persistent(env, &key, &history);
+}
+
+fn load_game(env: &Env, game_id: &Address) -> Result<GameInfo, Error> {
+    read_persistent(env, &DataKey::Game(game_id.clone())).ok_or(Error::GameNotRegistered)
+}
+
+fn set_suspended(env: &Env, game_id: Address, suspended: bool) -> Result<(), Error> {
+    admin(env).require_auth();
+    let mut game = load_game(env, &game_id)?;
+    game.suspended = suspended;
+    write_persistent(env, &DataKey::Game(game_id.clone()), &game);
+    GameStatusChanged { game_id, suspended }.publish(env);
+    Ok(())
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not set")
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct GameHub;
+
+#[contractimpl]
+impl GameHub {
+    /// Initialize the hub
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (credits points, can upgrade contract)
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    /// Start a game session and lock both players' points.
+    ///
+    /// Only the game contract itself can open a session: `game_id.require_auth()`
+    /// is satisfied by the calling contract's own address. The game must be
+    /// registered and not suspended. The game is responsible for getting each
+    /// player's consent to their stake.
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract calling this method
+    /// * `session_id` - Unique identifier for this game session
+    /// * `player1` - Address of first player
+    /// * `player2` - Address of second player
+    /// * `player1_points` - Points player 1 puts at stake
+    /// * `player2_points` - Points player 2 puts at stake
+    pub fn start_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        game_id.require_auth();
+
+        let game = load_game(&env, &game_id)?;
+        if game.suspended {
+            return Err(Error::GameSuspended);
+        }
+        if player1 == player2 {
+            return Err(Error::SelfPlay);
+        }
+        if player1_points < 0 || player2_points < 0 {
+            return Err(Error::InvalidPoints);
+        }
+        let key = DataKey::Session(session_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::SessionExists);
+        }
+
+        lock_points(&env, &player1, player1_points)?;
+        lock_points(&env, &player2, player2_points)?;
+
+        let session = Session {
+            session_id,
+            game_id: game_id.clone(),
+            player1: player1.clone(),
+            player2: player2.clone(),
+            player1_points,
+            player2_points,
+            start_ledger: env.ledger().sequence(),
+            end_ledger: None,
+            deadline_ledger: env.ledger().sequence() + game.session_ledgers,
+            waiting_on: None,
+            status: SessionStatus::Active,
+            winner: None,
+        };
+        write_persistent(&env, &key, &session);
+        add_active(&env, &player1, session_id);
+        add_active(&env, &player2, session_id);
+
+        GameStarted {
+            session_id,
+            game_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+        }
+        .publish(&env);
+        env.storage().instance().extend_ttl(PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+        Ok(())
+    }
+
+    /// End a game session, pay the pot to the winner and record the result.
+    ///
+    /// Only the game contract that started the session may end it, and only once.
+    /// Kept for games written against the original interface; it is
+    /// `end_game_with_outcome` with `Player1Won` or `Player2Won`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `player1_won` - True if player1 won, false if player2 won
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) -> Result<(), Error> {
+        let outcome = if player1_won { Outcome::Player1Won } else { Outcome::Player2Won };
+        settle(&env, session_id, outcome)
+    }
+
+    /// End a game session with any outcome.
+    ///
+    /// * `Player1Won` / `Player2Won` - the winner takes the whole pot
+    /// * `Draw` - each player gets their own stake back and a draw on their record
+    /// * `Cancelled` - stakes are refunded and standings are left alone
+    ///
+    /// Only the game contract that started the session may end it, and only once.
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `outcome` - How the game ended
+    pub fn end_game_with_outcome(env: Env, session_id: u32, outcome: Outcome) -> Result<(), Error> {
+        settle(&env, session_id, outcome)
+    }
+
+    // ========================================================================
+    // Deadlines
+    // ========================================================================
+
+    /// Move a session's deadline later, for games that run long. Only the game
+    /// that opened the session may extend it.
+    ///
+    /// # Arguments
+    /// * `session_id` - The running session
+    /// * `deadline_ledger` - New deadline; no earlier than the current one and
+    ///   at most 30 days of ledgers ahead
+    /// * `waiting_on` - The player who has to act next, if the game tracks turns;
+    ///   under the `Forfeit` rule they lose the session if it expires
+    pub fn extend_deadline(
+        env: Env,
+        session_id: u32,
+        deadline_ledger: u32,
+        waiting_on: Option<Address>,
+    ) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        session.game_id.require_auth();
+
+        if session.status != SessionStatus::Active {
+            return Err(Error::SessionEnded);
+        }
+        let latest = env.ledger().sequence() + MAX_SESSION_LEDGERS;
+        if deadline_ledger < session.deadline_ledger || deadline_ledger > latest {
+            return Err(Error::InvalidDeadline);
+        }
+        if let Some(player) = &waiting_on {
+            if *player != session.player1 && *player != session.player2 {
+                return Err(Error::NotAPlayer);
+            }
+        }
+
+        session.deadline_ledger = deadline_ledger;
+        session.waiting_on = waiting_on;
+        write_persistent(&env, &DataKey::Session(session_id), &session);
+        DeadlineExtended { session_id, deadline_ledger }.publish(&env);
+        Ok(())
+    }
+
+    /// Settle a session whose game never ended it. Anyone may call this once
+    /// the deadline has passed; the game's expiry rule decides whether both
+    /// stakes are refunded or the player the game was waiting on forfeits.
+    pub fn expire_session(env: Env, session_id: u32) -> Result<(), Error> {
+        let mut session = load_session(&env, session_id)?;
+        let ledger = env.ledger().sequence();
+        if session.status == SessionStatus::Active && ledger <= session.deadline_ledger {
+            return Err(Error::SessionNotExpired);
+        }
+
+        let rule = load_game(&env, &session.game_id)?.expiry_rule;
+        let outcome = expiry_outcome(&session, rule);
+        transition(&mut session, SessionStatus::Expired, ledger)?;
+        pay_out(&env, &mut session, outcome);
+        close(&env, &session);
+
+        SessionExpired { session_id, winner: session.winner.clone() }.publish(&env);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Game Registry
+    // ========================================================================
+
+    /// Approve a game contract to open sessions. Admin only.
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract
+    /// * `name` - Display name, e.g. "Lantern Chess"
+    /// * `version` - Game version, for clients that show it
+    pub fn add_game(env: Env, game_id: Address, name: String, version: u32) -> Result<(), Error> {
+        admin(&env).require_auth();
+        let key = DataKey::Game(game_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::GameAlreadyRegistered);
+        }
+        let game = GameInfo {
+            name: name.clone(),
+            version,
+            registered_ledger: env.ledger().sequence(),
+            suspended: false,
+            session_ledgers: DEFAULT_SESSION_LEDGERS,
+            expiry_rule: ExpiryRule::Refund,
+        };
+        write_persistent(&env, &key, &game);
+        GameRegistered { game_id, name, version }.publish(&env);
+        Ok(())
+    }
+
+    /// Update a registered game's name and version, e.g. after an upgrade. Admin only.
+    pub fn update_game(env: Env, game_id: Address, name: String, version: u32) -> Result<(), Error> {
+        admin(&env).require_auth();
+        let mut game = load_game(&env, &game_id)?;
+        game.name = name;
+        game.version = version;
+        write_persistent(&env, &DataKey::Game(game_id), &game);
+        Ok(())
+    }
+
+    /// Set how long a game's new sessions run before they can expire, and what
+    /// expiry does to them. Running sessions keep their deadlines. Admin only.
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract
+    /// * `session_ledgers` - Ledgers from `start_game` to the deadline (1 to 30 days' worth)
+    /// * `expiry_rule` - `Refund` or `Forfeit`
+    pub fn set_expiry_policy(
+        env: Env,
+        game_id: Address,
+        session_ledgers: u32,
+        expiry_rule: ExpiryRule,
+    ) -> Result<(), Error> {
+        admin(&env).require_auth();
+        if session_ledgers == 0 || session_ledgers > MAX_SESSION_LEDGERS {
+            return Err(Error::InvalidDeadline);
+        }
+        let mut game = load_game(&env, &game_id)?;
+        game.session_ledgers = session_ledgers;
+        game.expiry_rule = expiry_rule;
+        write_persistent(&env, &DataKey::Game(game_id), &game);
+        Ok(())
+    }
+
+    /// Stop a game from opening new sessions. Its running sessions can still
+    /// end, and other games are unaffected. Admin only.
+    pub fn suspend_game(env: Env, game_id: Address) -> Result<(), Error> {
+        set_suspended(&env, game_id, true)
+    }
+
+    /// Let a suspended game open sessions again. Admin only.
+    pub fn resume_game(env: Env, game_id: Address) -> Result<(), Error> {
+        set_suspended(&env, game_id, false)
+    }
+
+    /// Get a registered game's metadata and status.
+    pub fn get_game(env: Env, game_id: Address) -> Result<GameInfo, Error> {
+        load_game(&env, &game_id)
+    }
+
+    /// Whether `game_id` may open sessions right now.
+    pub fn is_game_active(env: Env, game_id: Address) -> bool {
+        load_game(&env, &game_id).is_ok_and(|game| !game.suspended)
+    }
+
+    // ========================================================================
+    // Points
+    // ========================================================================
+
+    /// Credit points to a player. Admin only.
+    ///
+    /// # Arguments
+    /// * `player` - Player to credit
+    /// * `amount` - Points to add (must be positive)
+    pub fn credit_points(env: Env, player: Address, amount: i128) -> Result<(), Error> {
+        admin(&env).require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidPoints);
+        }
+        set_balance(&env, &player, balance_of(&env, &player) + amount);
+        PointsCredited { player, amount }.publish(&env);
+        Ok(())
+    }
+
+    /// Points a player has available, not counting any locked in running sessions.
+    pub fn get_balance(env: Env, player: Address) -> i128 {
+        balance_of(&env, &player)
+    }
+
+    /// A player's win/loss record across every game on this hub.
+    pub fn get_standing(env: Env, player: Address) -> Standing {
+        standing_of(&env, &player)
+    }
+
+    // ========================================================================
+    // Ratings
+    // ========================================================================
+
+    /// A player's overall Elo rating across every game on this hub.
+    /// Players who haven't finished a rated game are at 1200, provisional.
+    pub fn get_rating(env: Env, player: Address) -> Rating {
+        rating_of(&env, &DataKey::Rating(player))
+    }
+
+    /// A player's Elo rating in one game.
+    pub fn get_game_rating(env: Env, game_id: Address, player: Address) -> Rating {
+        rating_of(&env, &DataKey::GameRating(game_id, player))
+    }
+
+    /// The player's most recent rating changes, newest first (at most 50).
+    /// Cancelled and refunded sessions aren't rated.
+    pub fn get_rating_history(env: Env, player: Address) -> Vec<RatingChange> {
+        read_persistent(&env, &DataKey::RatingHistory(player)).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    // ========================================================================
+    // Session Queries
+    // ========================================================================
+
+    /// Get a session, active or ended.
+    pub fn get_session(env: Env, session_id: u32) -> Result<Session, Error> {
+        load_session(&env, session_id)
+    }
+
+    /// Sessions the player is in that haven't ended yet, oldest first.
+    pub fn list_active_sessions(env: Env, player: Address) -> Vec<Session> {
+        load_sessions(&env, session_ids(&env, &DataKey::ActiveSessions(player)))
+    }
+
+    /// The player's most recent ended sessions, newest first (at most 50).
+    pub fn get_player_history(env: Env, player: Address) -> Vec<Session> {
+        load_sessions(&env, session_ids(&env, &DataKey::History(player)))
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    ///
+    /// # Returns
+    /// * `Address` - The admin address
+    pub fn get_admin(env: Env) -> Address {
+        admin(&env)
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) {
+        admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        admin(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,52 @@
+//! Integer Elo. Scores and expectations are per mille (1000 = a win,
+//! 500 = a draw), since contracts have no floating point.
+
+/// Rating a player starts at, overall and in every game
+pub const INITIAL_RATING: i32 = 1200;
+/// Ratings count as provisional until this many rated games
+pub const PROVISIONAL_GAMES: u32 = 20;
+
+/// Provisional ratings move twice as fast, so new players find their level
+const K_PROVISIONAL: i32 = 40;
+const K_ESTABLISHED: i32 = 20;
+
+/// Expected score (per mille) for a rating lead of 0, 25, 50, … 800 points:
+/// 1000 / (1 + 10^(-lead / 400)), rounded
+const EXPECTED: [i32; 33] = [
+    500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909, 920, 930, 939, 947,
+    954, 960, 965, 969, 973, 977, 980, 983, 985, 987, 989, 990,
+];
+const STEP: i32 = 25;
+const MAX_LEAD: i32 = STEP * (EXPECTED.len() as i32 - 1);
+
+pub const WIN: i32 = 1000;
+pub const DRAW: i32 = 500;
+pub const LOSS: i32 = 0;
+
+/// Expected score of a player rated `rating` against `opponent`, per mille.
+/// Leads past 800 points count as 800.
+pub fn expected_score(rating: i32, opponent: i32) -> i32 {
+    let lead = (rating - opponent).clamp(-MAX_LEAD, MAX_LEAD);
+    let (index, rest) = ((lead.abs() / STEP) as usize, lead.abs() % STEP);
+    let mut expected = EXPECTED[index];
+    if rest > 0 {
+        expected += (EXPECTED[index + 1] - expected) * rest / STEP;
+    }
+    if lead < 0 {
+        1000 - expected
+    } else {
+        expected
+    }
+}
+
+/// Rating change for a player with `games` rated games behind them who
+/// scored `score` (per mille) against `opponent`. Rounded to the nearest point.
+pub fn rating_delta(rating: i32, opponent: i32, games: u32, score: i32) -> i32 {
+    let k = if games < PROVISIONAL_GAMES { K_PROVISIONAL } else { K_ESTABLISHED };
+    let change = k * (score - expected_score(rating, opponent));
+    if change >= 0 {
+        (change + 500) / 1000
+    } else {
+        (change - 500) / 1000
+    }
+}
@@ -0,0 +1,867 @@
+#![cfg(test)]
+
+// Unit tests for the Game Hub, plus integration tests that run a real game
+// contract (dice-duel) against it.
+
+use crate::rating::{expected_score, rating_delta, DRAW, LOSS, WIN};
+use crate::{Error, ExpiryRule, GameHub, GameHubClient, Outcome, SessionStatus, Standing};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{Address, Env, String};
+
+const STARTING_POINTS: i128 = 1_000;
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+fn setup_test() -> (
+    Env,
+    GameHubClient<'static>,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(GameHub, (&admin,));
+    let hub = GameHubClient::new(&env, &hub_addr);
+
+    // Stands in for a game contract; mock_all_auths satisfies its require_auth
+    let game = Address::generate(&env);
+    hub.add_game(&game, &String::from_str(&env, "Test Game"), &1);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    hub.credit_points(&player1, &STARTING_POINTS);
+    hub.credit_points(&player2, &STARTING_POINTS);
+
+    (env, hub, game, player1, player2)
+}
+
+/// Deploy dice-duel against `hub` and register it there.
+fn register_dice_duel(env: &Env, hub: &GameHubClient<'static>) -> DiceDuelContractClient<'static> {
+    let dice_addr = env.register(DiceDuelContract, (&hub.get_admin(), &hub.address));
+    hub.add_game(&dice_addr, &String::from_str(env, "Dice Duel"), &1);
+    DiceDuelContractClient::new(env, &dice_addr)
+}
+
+/// Assert that a Result contains a specific hub error
+///
+/// The try_ methods return: `Result<Result<T, T::Error>, Result<E, InvokeError>>`
+/// - Err(Ok(error)): Contract reverted with custom error (THIS IS WHAT WE TEST)
+fn assert_hub_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Escrow and Payout Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_locks_points() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS - 100);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS - 250);
+
+    let session = hub.get_session(&1);
+    assert_eq!(session.game_id, game);
+    assert_eq!(session.player1_points, 100);
+    assert_eq!(session.player2_points, 250);
+    assert!(session.winner.is_none());
+}
+
+#[test]
+fn test_end_game_pays_the_pot_to_the_winner() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+    hub.end_game(&1, &false);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS - 100);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS + 100);
+    assert_eq!(hub.get_session(&1).winner, Some(player2));
+}
+
+#[test]
+fn test_end_game_updates_standings() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+    hub.start_game(&game, &2, &player1, &player2, &50, &50);
+    hub.end_game(&2, &true);
+
+    assert_eq!(
+        hub.get_standing(&player1),
+        Standing { wins: 2, losses: 0, draws: 0, points_won: 150, points_lost: 0 }
+    );
+    assert_eq!(
+        hub.get_standing(&player2),
+        Standing { wins: 0, losses: 2, draws: 0, points_won: 0, points_lost: 150 }
+    );
+}
+
+#[test]
+fn test_zero_point_games_are_allowed() {
+    let (env, hub, game, player1, _player2) = setup_test();
+    let newcomer = Address::generate(&env);
+
+    hub.start_game(&game, &1, &player1, &newcomer, &0, &0);
+    hub.end_game(&1, &false);
+
+    assert_eq!(hub.get_balance(&newcomer), 0);
+    assert_eq!(hub.get_standing(&newcomer).wins, 1);
+}
+
+#[test]
+fn test_outcome_win_pays_the_pot() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+    hub.end_game_with_outcome(&1, &Outcome::Player2Won);
+
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS + 100);
+    let session = hub.get_session(&1);
+    assert_eq!(session.status, SessionStatus::Ended);
+    assert_eq!(session.winner, Some(player2.clone()));
+    assert_eq!(hub.get_standing(&player2).wins, 1);
+    assert_eq!(hub.get_standing(&player1).points_lost, 100);
+}
+
+#[test]
+fn test_draw_returns_stakes_and_records_a_draw() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+    hub.end_game_with_outcome(&1, &Outcome::Draw);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS);
+    assert_eq!(hub.get_standing(&player1).draws, 1);
+    assert_eq!(hub.get_standing(&player2).draws, 1);
+    assert_eq!(hub.get_standing(&player1).losses, 0);
+
+    let session = hub.get_session(&1);
+    assert_eq!(session.status, SessionStatus::Ended);
+    assert_eq!(session.winner, None);
+}
+
+#[test]
+fn test_cancel_refunds_without_touching_standings() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+    hub.end_game_with_outcome(&1, &Outcome::Cancelled);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS);
+    assert_eq!(hub.get_standing(&player1), Standing::default());
+    assert_eq!(hub.get_standing(&player2), Standing::default());
+
+    let session = hub.get_session(&1);
+    assert_eq!(session.status, SessionStatus::Cancelled);
+    assert_eq!(session.winner, None);
+    assert_eq!(hub.list_active_sessions(&player1).len(), 0);
+    assert_eq!(hub.get_player_history(&player1).len(), 1);
+}
+
+#[test]
+fn test_cancelled_session_is_final() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game_with_outcome(&1, &Outcome::Cancelled);
+
+    assert_hub_error(&hub.try_end_game(&1, &true), Error::SessionEnded);
+    assert_hub_error(&hub.try_end_game_with_outcome(&1, &Outcome::Draw), Error::SessionEnded);
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+}
+
+// ============================================================================
+// Error Handling Tests
+// ============================================================================
+
+#[test]
+fn test_insufficient_points() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    let result = hub.try_start_game(&game, &1, &player1, &player2, &100, &(STARTING_POINTS + 1));
+    assert_hub_error(&result, Error::InsufficientPoints);
+
+    // Nothing was locked for player1 either
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+}
+
+#[test]
+fn test_negative_points_rejected() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    let result = hub.try_start_game(&game, &1, &player1, &player2, &-1, &100);
+    assert_hub_error(&result, Error::InvalidPoints);
+}
+
+#[test]
+fn test_self_play_rejected() {
+    let (_env, hub, game, player1, _player2) = setup_test();
+
+    let result = hub.try_start_game(&game, &1, &player1, &player1, &100, &100);
+    assert_hub_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_duplicate_session_rejected() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    let result = hub.try_start_game(&game, &1, &player1, &player2, &100, &100);
+    assert_hub_error(&result, Error::SessionExists);
+}
+
+#[test]
+fn test_unknown_session_rejected() {
+    let (_env, hub, _game, _player1, _player2) = setup_test();
+
+    assert_hub_error(&hub.try_end_game(&42, &true), Error::SessionNotFound);
+    assert_hub_error(&hub.try_get_session(&42), Error::SessionNotFound);
+}
+
+#[test]
+fn test_double_end_rejected() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+
+    let result = hub.try_end_game(&1, &false);
+    assert_hub_error(&result, Error::SessionEnded);
+
+    // The second call paid nothing out
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS + 100);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS - 100);
+}
+
+// ============================================================================
+// Session Lifecycle Tests
+// ============================================================================
+
+#[test]
+fn test_session_records_its_lifecycle() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &5, &player1, &player2, &100, &100);
+    let session = hub.get_session(&5);
+    assert_eq!(session.session_id, 5);
+    assert_eq!(session.player1, player1);
+    assert_eq!(session.player2, player2);
+    assert_eq!(session.start_ledger, 100);
+    assert_eq!(session.end_ledger, None);
+    assert_eq!(session.status, SessionStatus::Active);
+
+    env.ledger().with_mut(|li| li.sequence_number = 150);
+    hub.end_game(&5, &true);
+    let session = hub.get_session(&5);
+    assert_eq!(session.status, SessionStatus::Ended);
+    assert_eq!(session.end_ledger, Some(150));
+    assert_eq!(session.winner, Some(player1));
+}
+
+#[test]
+fn test_ended_session_id_cannot_be_reused() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+
+    let result = hub.try_start_game(&game, &1, &player1, &player2, &100, &100);
+    assert_hub_error(&result, Error::SessionExists);
+    assert_eq!(hub.get_session(&1).status, SessionStatus::Ended);
+}
+
+#[test]
+fn test_session_ids_are_unique_across_games() {
+    let (env, hub, game, player1, player2) = setup_test();
+    let other = Address::generate(&env);
+    hub.add_game(&other, &String::from_str(&env, "Other"), &1);
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    let result = hub.try_start_game(&other, &1, &player1, &player2, &100, &100);
+    assert_hub_error(&result, Error::SessionExists);
+    assert_eq!(hub.get_session(&1).game_id, game);
+}
+
+#[test]
+fn test_list_active_sessions() {
+    let (env, hub, game, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+    hub.credit_points(&player3, &STARTING_POINTS);
+
+    hub.start_game(&game, &1, &player1, &player2, &10, &10);
+    hub.start_game(&game, &2, &player1, &player3, &10, &10);
+    hub.start_game(&game, &3, &player2, &player3, &10, &10);
+
+    let active = hub.list_active_sessions(&player1);
+    assert_eq!(active.len(), 2);
+    assert_eq!(active.get(0).unwrap().session_id, 1);
+    assert_eq!(active.get(1).unwrap().session_id, 2);
+
+    hub.end_game(&1, &true);
+    let active = hub.list_active_sessions(&player1);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap().session_id, 2);
+    assert_eq!(hub.list_active_sessions(&player2).len(), 1);
+    assert_eq!(hub.list_active_sessions(&Address::generate(&env)).len(), 0);
+}
+
+#[test]
+fn test_player_history_is_newest_first() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    for session_id in 1..=3u32 {
+        hub.start_game(&game, &session_id, &player1, &player2, &10, &10);
+        hub.end_game(&session_id, &(session_id % 2 == 1));
+    }
+    hub.start_game(&game, &4, &player1, &player2, &10, &10);
+
+    let history = hub.get_player_history(&player2);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().session_id, 3);
+    assert_eq!(history.get(1).unwrap().session_id, 2);
+    assert_eq!(history.get(2).unwrap().session_id, 1);
+    assert_eq!(history.get(1).unwrap().winner, Some(player2.clone()));
+    assert!(history.iter().all(|s| s.status == SessionStatus::Ended));
+}
+
+#[test]
+fn test_player_history_is_capped() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    for session_id in 1..=55u32 {
+        hub.start_game(&game, &session_id, &player1, &player2, &0, &0);
+        hub.end_game(&session_id, &true);
+    }
+
+    let history = hub.get_player_history(&player1);
+    assert_eq!(history.len(), 50);
+    assert_eq!(history.get(0).unwrap().session_id, 55);
+    assert_eq!(history.get(49).unwrap().session_id, 6);
+
+    // Dropped from the list, still readable directly
+    assert_eq!(hub.get_session(&1).status, SessionStatus::Ended);
+}
+
+// ============================================================================
+// Deadline and Expiry Tests
+// ============================================================================
+
+fn advance_to(env: &Env, sequence_number: u32) {
+    env.ledger().with_mut(|ledger| ledger.sequence_number = sequence_number);
+}
+
+#[test]
+fn test_session_gets_the_default_deadline() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+
+    let session = hub.get_session(&1);
+    assert_eq!(session.deadline_ledger, 100 + 120_960);
+    assert_eq!(session.waiting_on, None);
+    let info = hub.get_game(&game);
+    assert_eq!(info.session_ledgers, 120_960);
+    assert_eq!(info.expiry_rule, ExpiryRule::Refund);
+}
+
+#[test]
+fn test_expire_before_deadline_rejected() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+
+    advance_to(&env, 150);
+    assert_hub_error(&hub.try_expire_session(&1), Error::SessionNotExpired);
+    assert_eq!(hub.get_session(&1).status, SessionStatus::Active);
+}
+
+#[test]
+fn test_expired_session_is_refunded() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+
+    advance_to(&env, 151);
+    env.set_auths(&[]); // anyone can expire a session
+    hub.expire_session(&1);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS);
+    assert_eq!(hub.get_standing(&player1), Standing::default());
+
+    let session = hub.get_session(&1);
+    assert_eq!(session.status, SessionStatus::Expired);
+    assert_eq!(session.end_ledger, Some(151));
+    assert_eq!(session.winner, None);
+    assert_eq!(hub.list_active_sessions(&player1).len(), 0);
+    assert_eq!(hub.get_player_history(&player2).len(), 1);
+}
+
+#[test]
+fn test_forfeit_rule_awards_the_pot_to_the_waiting_player() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Forfeit);
+    hub.start_game(&game, &1, &player1, &player2, &100, &250);
+    hub.extend_deadline(&1, &200, &Some(player2.clone()));
+
+    advance_to(&env, 201);
+    hub.expire_session(&1);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS + 250);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS - 250);
+    assert_eq!(hub.get_standing(&player2).losses, 1);
+    assert_eq!(hub.get_session(&1).winner, Some(player1));
+}
+
+#[test]
+fn test_forfeit_rule_refunds_when_nobody_was_on_the_move() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Forfeit);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+
+    advance_to(&env, 151);
+    hub.expire_session(&1);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+    assert_eq!(hub.get_session(&1).winner, None);
+}
+
+#[test]
+fn test_extend_deadline_keeps_a_long_game_alive() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.extend_deadline(&1, &500, &None);
+    assert_eq!(env.auths()[0].0, game);
+
+    advance_to(&env, 400);
+    assert_hub_error(&hub.try_expire_session(&1), Error::SessionNotExpired);
+    hub.end_game(&1, &true);
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS + 100);
+}
+
+#[test]
+fn test_extend_deadline_rejects_bad_requests() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+
+    assert_hub_error(&hub.try_extend_deadline(&1, &149, &None), Error::InvalidDeadline);
+    assert_hub_error(&hub.try_extend_deadline(&1, &(100 + 518_401), &None), Error::InvalidDeadline);
+    let stranger = Address::generate(&env);
+    assert_hub_error(&hub.try_extend_deadline(&1, &200, &Some(stranger)), Error::NotAPlayer);
+
+    hub.end_game(&1, &true);
+    assert_hub_error(&hub.try_extend_deadline(&1, &200, &None), Error::SessionEnded);
+}
+
+#[test]
+fn test_ended_session_cannot_expire() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+
+    advance_to(&env, 151);
+    assert_hub_error(&hub.try_expire_session(&1), Error::SessionEnded);
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS + 100);
+}
+
+#[test]
+fn test_expired_session_cannot_be_ended() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Refund);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    advance_to(&env, 151);
+    hub.expire_session(&1);
+
+    assert_hub_error(&hub.try_end_game(&1, &true), Error::SessionEnded);
+    assert_hub_error(&hub.try_expire_session(&1), Error::SessionEnded);
+}
+
+#[test]
+fn test_expiry_policy_requires_admin_and_a_sane_length() {
+    let (env, hub, game, _player1, _player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &1_000, &ExpiryRule::Forfeit);
+    assert_eq!(env.auths()[0].0, hub.get_admin());
+    assert_eq!(hub.get_game(&game).expiry_rule, ExpiryRule::Forfeit);
+
+    assert_hub_error(&hub.try_set_expiry_policy(&game, &0, &ExpiryRule::Refund), Error::InvalidDeadline);
+    assert_hub_error(
+        &hub.try_set_expiry_policy(&game, &518_401, &ExpiryRule::Refund),
+        Error::InvalidDeadline,
+    );
+}
+
+// ============================================================================
+// Rating Tests
+// ============================================================================
+
+#[test]
+fn test_expected_score_table() {
+    assert_eq!(expected_score(1200, 1200), 500);
+    assert_eq!(expected_score(1600, 1200), 909);
+    assert_eq!(expected_score(1200, 1600), 91);
+    assert_eq!(expected_score(1210, 1200), 514); // interpolated between 500 and 536
+    assert_eq!(expected_score(3000, 1200), 990); // leads past 800 count as 800
+    assert_eq!(expected_score(1200, 3000), 10);
+}
+
+#[test]
+fn test_rating_delta() {
+    assert_eq!(rating_delta(1200, 1200, 0, WIN), 20);
+    assert_eq!(rating_delta(1200, 1200, 0, LOSS), -20);
+    assert_eq!(rating_delta(1200, 1200, 0, DRAW), 0);
+    assert_eq!(rating_delta(1200, 1200, 20, WIN), 10); // established ratings move half as fast
+    assert_eq!(rating_delta(1600, 1200, 20, WIN), 2);
+    assert_eq!(rating_delta(1200, 1600, 20, WIN), 18);
+    assert_eq!(rating_delta(1200, 1600, 20, DRAW), 8);
+}
+
+#[test]
+fn test_new_players_start_provisional() {
+    let (env, hub, game, player1, _player2) = setup_test();
+
+    let rating = hub.get_rating(&player1);
+    assert_eq!(rating.rating, 1200);
+    assert_eq!(rating.games, 0);
+    assert!(rating.provisional);
+    assert_eq!(hub.get_game_rating(&game, &player1), rating);
+    assert_eq!(hub.get_rating_history(&Address::generate(&env)).len(), 0);
+}
+
+#[test]
+fn test_end_game_updates_ratings() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+
+    assert_eq!(hub.get_rating(&player1).rating, 1220);
+    assert_eq!(hub.get_rating(&player2).rating, 1180);
+    assert_eq!(hub.get_game_rating(&game, &player1).rating, 1220);
+    assert_eq!(hub.get_rating(&player1).games, 1);
+
+    let change = hub.get_rating_history(&player2).get(0).unwrap();
+    assert_eq!(change.session_id, 1);
+    assert_eq!(change.game_id, game);
+    assert_eq!(change.overall_rating, 1180);
+    assert_eq!(change.overall_delta, -20);
+    assert_eq!(change.game_delta, -20);
+}
+
+#[test]
+fn test_game_ratings_are_kept_apart() {
+    let (env, hub, game, player1, player2) = setup_test();
+    let other = Address::generate(&env);
+    hub.add_game(&other, &String::from_str(&env, "Other Game"), &1);
+
+    hub.start_game(&game, &1, &player1, &player2, &10, &10);
+    hub.end_game(&1, &true);
+    hub.start_game(&other, &2, &player1, &player2, &10, &10);
+    hub.end_game(&2, &true);
+
+    // Overall, player1 led by 40 going into the second game, so gained less
+    assert_eq!(hub.get_rating(&player1).rating, 1238);
+    assert_eq!(hub.get_game_rating(&game, &player1).rating, 1220);
+    assert_eq!(hub.get_game_rating(&other, &player1).rating, 1220);
+    assert_eq!(hub.get_game_rating(&other, &player2).games, 1);
+
+    let history = hub.get_rating_history(&player1);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().session_id, 2); // newest first
+    assert_eq!(history.get(0).unwrap().overall_delta, 18);
+}
+
+#[test]
+fn test_draws_are_rated_and_cancellations_are_not() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &10, &10);
+    hub.end_game(&1, &true);
+    hub.start_game(&game, &2, &player1, &player2, &10, &10);
+    hub.end_game_with_outcome(&2, &Outcome::Draw);
+
+    // The underdog gains from a draw
+    assert_eq!(hub.get_rating(&player2).rating, 1182);
+    assert_eq!(hub.get_rating(&player1).rating, 1218);
+    assert_eq!(hub.get_rating(&player1).games, 2);
+
+    hub.start_game(&game, &3, &player1, &player2, &10, &10);
+    hub.end_game_with_outcome(&3, &Outcome::Cancelled);
+    assert_eq!(hub.get_rating(&player1).games, 2);
+    assert_eq!(hub.get_rating_history(&player1).len(), 2);
+}
+
+#[test]
+fn test_forfeit_is_rated() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.set_expiry_policy(&game, &50, &ExpiryRule::Forfeit);
+    hub.start_game(&game, &1, &player1, &player2, &10, &10);
+    hub.extend_deadline(&1, &150, &Some(player1.clone()));
+    advance_to(&env, 151);
+    hub.expire_session(&1);
+
+    assert_eq!(hub.get_rating(&player1).rating, 1180);
+    assert_eq!(hub.get_rating(&player2).rating, 1220);
+}
+
+#[test]
+fn test_rating_stops_being_provisional() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    for session_id in 1..=20 {
+        hub.start_game(&game, &session_id, &player1, &player2, &0, &0);
+        hub.end_game_with_outcome(&session_id, &Outcome::Draw);
+    }
+    let rating = hub.get_rating(&player1);
+    assert_eq!(rating.games, 20);
+    assert!(!rating.provisional);
+    assert_eq!(hub.get_rating_history(&player1).len(), 20);
+
+    hub.start_game(&game, &21, &player1, &player2, &0, &0);
+    hub.end_game(&21, &true);
+    assert_eq!(hub.get_rating(&player1).rating, 1210);
+}
+
+// ============================================================================
+// Game Registry Tests
+// ============================================================================
+
+#[test]
+fn test_add_game_stores_metadata() {
+    let (env, hub, game, _player1, _player2) = setup_test();
+
+    let info = hub.get_game(&game);
+    assert_eq!(info.name, String::from_str(&env, "Test Game"));
+    assert_eq!(info.version, 1);
+    assert_eq!(info.registered_ledger, 100);
+    assert!(!info.suspended);
+    assert!(hub.is_game_active(&game));
+
+    hub.update_game(&game, &String::from_str(&env, "Test Game"), &2);
+    assert_eq!(hub.get_game(&game).version, 2);
+
+    let result = hub.try_add_game(&game, &String::from_str(&env, "Again"), &3);
+    assert_hub_error(&result, Error::GameAlreadyRegistered);
+}
+
+#[test]
+fn test_unregistered_game_cannot_start_sessions() {
+    let (env, hub, _game, player1, player2) = setup_test();
+    let stranger = Address::generate(&env);
+
+    let result = hub.try_start_game(&stranger, &1, &player1, &player2, &100, &100);
+    assert_hub_error(&result, Error::GameNotRegistered);
+    assert!(!hub.is_game_active(&stranger));
+    assert_hub_error(&hub.try_get_game(&stranger), Error::GameNotRegistered);
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS);
+}
+
+#[test]
+fn test_suspended_game_cannot_start_sessions() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.suspend_game(&game);
+    assert!(!hub.is_game_active(&game));
+    let result = hub.try_start_game(&game, &1, &player1, &player2, &100, &100);
+    assert_hub_error(&result, Error::GameSuspended);
+
+    hub.resume_game(&game);
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+}
+
+#[test]
+fn test_suspended_game_can_still_end_running_sessions() {
+    let (_env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.suspend_game(&game);
+    hub.end_game(&1, &true);
+
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS + 100);
+}
+
+#[test]
+fn test_suspending_one_game_leaves_others_running() {
+    let (env, hub, game, player1, player2) = setup_test();
+    let dice = register_dice_duel(&env, &hub);
+
+    hub.suspend_game(&game);
+    dice.start_game(&1, &player1, &player2, &10, &10);
+    dice.roll(&1, &player1);
+    dice.roll(&1, &player2);
+    dice.reveal_winner(&1);
+
+    assert!(hub.get_session(&1).winner.is_some());
+}
+
+#[test]
+fn test_registry_changes_require_admin() {
+    let (env, hub, game, _player1, _player2) = setup_test();
+    let admin = hub.get_admin();
+
+    hub.suspend_game(&game);
+    assert_eq!(env.auths()[0].0, admin);
+
+    let other = Address::generate(&env);
+    hub.add_game(&other, &String::from_str(&env, "Other"), &1);
+    assert_eq!(env.auths()[0].0, admin);
+
+    env.set_auths(&[]);
+    assert!(hub.try_resume_game(&game).is_err());
+}
+
+// ============================================================================
+// Authorization Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_requires_game_auth() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, game);
+}
+
+#[test]
+fn test_end_game_requires_the_starting_game() {
+    let (env, hub, game, player1, player2) = setup_test();
+
+    hub.start_game(&game, &1, &player1, &player2, &100, &100);
+    hub.end_game(&1, &true);
+
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, game);
+}
+
+#[test]
+fn test_start_game_without_auth_fails() {
+    let (env, hub, game, player1, player2) = setup_test();
+    env.set_auths(&[]);
+
+    assert!(hub.try_start_game(&game, &1, &player1, &player2, &100, &100).is_err());
+}
+
+#[test]
+fn test_credit_points_requires_admin() {
+    let (env, hub, _game, player1, _player2) = setup_test();
+
+    hub.credit_points(&player1, &5);
+    let auths = env.auths();
+    assert_eq!(auths[0].0, hub.get_admin());
+
+    assert_hub_error(&hub.try_credit_points(&player1, &0), Error::InvalidPoints);
+}
+
+// ============================================================================
+// Integration Tests (dice-duel against the real hub)
+// ============================================================================
+
+#[test]
+fn test_dice_duel_settles_through_the_hub() {
+    let (env, hub, _game, player1, player2) = setup_test();
+
+    let dice = register_dice_duel(&env, &hub);
+    let dice_addr = dice.address.clone();
+
+    dice.start_game(&1, &player1, &player2, &300, &200);
+    assert_eq!(hub.get_session(&1).game_id, dice_addr);
+    assert_eq!(hub.get_balance(&player1), STARTING_POINTS - 300);
+    assert_eq!(hub.get_balance(&player2), STARTING_POINTS - 200);
+
+    dice.roll(&1, &player1);
+    dice.roll(&1, &player2);
+    let winner = dice.reveal_winner(&1);
+
+    let (winner_gain, loser, loser_loss) = if winner == player1 {
+        (200, player2.clone(), 200)
+    } else {
+        (300, player1.clone(), 300)
+    };
+    assert_eq!(hub.get_balance(&winner), STARTING_POINTS + winner_gain);
+    assert_eq!(hub.get_balance(&loser), STARTING_POINTS - loser_loss);
+    assert_eq!(hub.get_standing(&winner).wins, 1);
+    assert_eq!(hub.get_standing(&loser).losses, 1);
+
+    // Revealing again returns the stored winner without touching the hub
+    assert_eq!(dice.reveal_winner(&1), winner);
+    assert_eq!(hub.get_balance(&winner), STARTING_POINTS + winner_gain);
+}
+
+#[test]
+fn test_dice_duel_cannot_reuse_a_session() {
+    let (env, hub, _game, player1, player2) = setup_test();
+
+    let dice = register_dice_duel(&env, &hub);
+
+    dice.start_game(&7, &player1, &player2, &10, &10);
+    assert!(dice.try_start_game(&7, &player1, &player2, &10, &10).is_err());
+}
@@ -1,31 +1,19 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractevent, contractimpl, Address, Env};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+// Interface, `Outcome` and events shared with game-hub
+use game_hub_client::{DeadlineExtended, GameEnded, GameSettled, GameStarted};
+pub use game_hub_client::Outcome;
 
 /// Mock Game Hub contract for game studio development
 ///
 /// This contract provides the same external interface that games expect
-/// (start_game, end_game) but does nothing internally. It exists purely
+/// (start_game, end_game, end_game_with_outcome, extend_deadline) but does nothing internally. It exists purely
 /// for game contracts to compile and integrate during development.
 #[contract]
 pub struct MockGameHub;
 
-#[contractevent]
-pub struct GameStarted {
-    pub session_id: u32,
-    pub game_id: Address,
-    pub player1: Address,
-    pub player2: Address,
-    pub player1_points: i128,
-    pub player2_points: i128,
-}
-
-#[contractevent]
-pub struct GameEnded {
-    pub session_id: u32,
-    pub player1_won: bool,
-}
-
 #[contractimpl]
 impl MockGameHub {
     /// Start a game session
@@ -77,6 +65,28 @@ impl MockGameHub {
         }
         .publish(&env);
     }
+
+    /// End a game session with a win, draw or cancellation
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `outcome` - How the game ended
+    pub fn end_game_with_outcome(env: Env, session_id: u32, outcome: Outcome) {
+        // No auth required for mock
+        GameSettled { session_id, outcome }.publish(&env);
+    }
+
+    /// Move a session's deadline later
+    ///
+    /// # Arguments
+    /// * `session_id` - The running session
+    /// * `deadline_ledger` - New deadline ledger
+    /// * `waiting_on` - Player who has to act next (ignored in mock)
+    pub fn extend_deadline(env: Env, session_id: u32, deadline_ledger: u32, waiting_on: Option<Address>) {
+        // No auth required for mock
+        let _ = waiting_on;
+        DeadlineExtended { session_id, deadline_ledger }.publish(&env);
+    }
 }
 
 #[cfg(test)]
@@ -93,5 +103,8 @@ mod test {
         let player2 = Address::generate(&env);
         client.start_game(&game_id, &1, &player1, &player2, &1000, &1000);
         client.end_game(&1, &true);
+        client.start_game(&game_id, &2, &player1, &player2, &1000, &1000);
+        client.extend_deadline(&2, &500, &Some(player1.clone()));
+        client.end_game_with_outcome(&2, &Outcome::Draw);
     }
 }
@@ -10,29 +10,11 @@
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, contract, contracterror, contractimpl, contracttype, vec
 };
 
-// Import GameHub contract interface
-// This allows us to call into the GameHub contract
-#[contractclient(name = "GameHubClient")]
-pub trait GameHub {
-    fn start_game(
-        env: Env,
-        game_id: Address,
-        session_id: u32,
-        player1: Address,
-        player2: Address,
-        player1_points: i128,
-        player2_points: i128,
-    );
-
-    fn end_game(
-        env: Env,
-        session_id: u32,
-        player1_won: bool
-    );
-}
+// Game Hub interface shared by every game
+use game_hub_client::GameHubClient;
 
 // ============================================================================
 // Errors
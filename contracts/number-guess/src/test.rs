@@ -1,44 +1,16 @@
 #![cfg(test)]
 
-// Unit tests for the number-guess contract using a simple mock GameHub.
+// Unit tests for the number-guess contract against the shared RecordingGameHub
+// from game-hub-client.
 // These tests verify game logic independently of the full GameHub system.
 //
-// Note: These tests use a minimal mock for isolation and speed.
+// Note: The recording hub keeps calls but does no session bookkeeping.
 // For full integration tests with the real Game Hub contract, see the platform repo.
 
 use crate::{Error, NumberGuessContract, NumberGuessContractClient};
+use game_hub_client::testutils::{HubCall, RecordingGameHub, RecordingGameHubClient, StartGameCall};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
-
-// ============================================================================
-// Mock GameHub for Unit Testing
-// ============================================================================
-
-#[contract]
-pub struct MockGameHub;
-
-#[contractimpl]
-impl MockGameHub {
-    pub fn start_game(
-        _env: Env,
-        _game_id: Address,
-        _session_id: u32,
-        _player1: Address,
-        _player2: Address,
-        _player1_points: i128,
-        _player2_points: i128,
-    ) {
-        // Mock implementation - does nothing
-    }
-
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
-        // Mock implementation - does nothing
-    }
-
-    pub fn add_game(_env: Env, _game_address: Address) {
-        // Mock implementation - does nothing
-    }
-}
+use soroban_sdk::{vec, Address, BytesN, Env};
 
 // ============================================================================
 // Test Helpers
@@ -47,7 +19,7 @@ impl MockGameHub {
 fn setup_test() -> (
     Env,
     NumberGuessContractClient<'static>,
-    MockGameHubClient<'static>,
+    RecordingGameHubClient<'static>,
     Address,
     Address,
 ) {
@@ -66,9 +38,9 @@ fn setup_test() -> (
         max_entry_ttl: u32::MAX / 2,
     });
 
-    // Deploy mock GameHub contract
-    let hub_addr = env.register(MockGameHub, ());
-    let game_hub = MockGameHubClient::new(&env, &hub_addr);
+    // Deploy recording GameHub contract
+    let hub_addr = env.register(RecordingGameHub, ());
+    let game_hub = RecordingGameHubClient::new(&env, &hub_addr);
 
     // Create admin address
     let admin = Address::generate(&env);
@@ -77,9 +49,6 @@ fn setup_test() -> (
     let contract_id = env.register(NumberGuessContract, (&admin, &hub_addr));
     let client = NumberGuessContractClient::new(&env, &contract_id);
 
-    // Register number-guess as a whitelisted game (mock does nothing)
-    game_hub.add_game(&contract_id);
-
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
@@ -520,7 +489,7 @@ fn test_upgrade_function_exists() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
 
     // Deploy number-guess with admin
     let contract_id = env.register(NumberGuessContract, (&admin, &hub_addr));
@@ -537,3 +506,83 @@ fn test_upgrade_function_exists() {
     // This confirms the authorization check passed
     assert!(result.is_err());
 }
+
+// ============================================================================
+// Game Hub Call Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_reports_session_to_hub() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    client.start_game(&20u32, &player1, &player2, &100_0000000, &50_0000000);
+
+    let start = StartGameCall {
+        game_id: client.address.clone(),
+        session_id: 20,
+        player1,
+        player2,
+        player1_points: 100_0000000,
+        player2_points: 50_0000000,
+    };
+    assert_eq!(hub.calls(), vec![&env, HubCall::StartGame(start)]);
+}
+
+#[test]
+fn test_reveal_reports_stored_winner_to_hub() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 21u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+    let winner = client.reveal_winner(&session_id);
+
+    let winning_number = client.get_game(&session_id).winning_number.unwrap();
+    assert_eq!(winner == player1, winning_number.abs_diff(3) <= winning_number.abs_diff(8));
+
+    let calls = hub.calls_for(&session_id);
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls.get(1), Some(HubCall::EndGame(session_id, winner == player1)));
+
+    // Revealing again returns the stored winner without ending the session twice
+    client.reveal_winner(&session_id);
+    assert_eq!(hub.call_count(), 2);
+}
+
+#[test]
+fn test_failed_hub_start_stores_no_game() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    hub.set_failing(&true);
+    let result = client.try_start_game(&22u32, &player1, &player2, &100_0000000, &100_0000000);
+    assert!(result.is_err());
+
+    let result = client.try_get_game(&22u32);
+    assert_number_guess_error(&result, Error::GameNotFound);
+    assert_eq!(hub.call_count(), 0);
+}
+
+#[test]
+fn test_failed_hub_end_rolls_back_reveal() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 23u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+
+    hub.set_failing(&true);
+    assert!(client.try_reveal_winner(&session_id).is_err());
+
+    // Neither the winner nor the winning number were kept
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert!(game.winning_number.is_none());
+    assert_eq!(hub.player1_won(&session_id), None);
+
+    // Once the hub accepts the call, the same reveal goes through
+    hub.set_failing(&false);
+    let winner = client.reveal_winner(&session_id);
+    assert_eq!(hub.player1_won(&session_id), Some(winner == player1));
+}
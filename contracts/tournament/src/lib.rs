@@ -0,0 +1,941 @@
+#![no_std]
+
+//! # Tournament
+//!
+//! Brackets on top of the Game Hub. An organizer creates a tournament for one
+//! game registered on the hub, players register and pay the entry fee in a
+//! token, and the organizer starts it when registration closes. Each pairing
+//! becomes a hub session started through the game contract; `report_result`
+//! reads the finished session back from the hub and advances the winner. When
+//! the last round is decided the prize pool is paid out by final standing.
+//!
+//! Formats:
+//! * Single elimination - seeded by registration order, with byes for the top
+//!   seeds when the field isn't a power of two. A match that ends without a
+//!   winner (draw, cancellation, refunded expiry) is replayed.
+//! * Swiss - a fixed number of rounds. Players are paired with others on the
+//!   same score, avoiding rematches where possible; a draw scores half a win.
+//!
+//! Every match has a deadline. Players `check_in` to say they are ready, and
+//! once the deadline passes without the match starting, `claim_forfeit`
+//! settles it in favour of whoever checked in. Elimination replays are capped
+//! too, so no round can stall for good; failing all else the admin can
+//! `abort` a tournament and refund every entry fee.
+//!
+//! The game must expose the standard `start_game(session_id, player1, player2,
+//! player1_points, player2_points)`. Both players authorize `start_match`,
+//! as they would a game's own `start_game`, and matches stake no hub points.
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, token, xdr::ToXdr,
+    Address, BytesN, Env, Map, Vec,
+};
+
+// The tournament only checks games and reads finished sessions from the hub
+use game_hub_client::{GameHubReaderClient, SessionStatus};
+
+// Standard game interface, as in dice-duel, twenty-one and number-guess
+#[contractclient(name = "GameClient")]
+pub trait Game {
+    fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    );
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    TournamentNotFound = 1,
+    GameNotRegistered = 2,
+    InvalidFormat = 3,
+    InvalidPayouts = 4,
+    InvalidEntryFee = 5,
+    RegistrationClosed = 6,
+    TournamentFull = 7,
+    AlreadyRegistered = 8,
+    NotEnoughEntrants = 9,
+    NotRunning = 10,
+    MatchNotFound = 11,
+    MatchNotPending = 12,
+    MatchNotStarted = 13,
+    MatchInProgress = 14,
+    DeadlineNotReached = 15,
+    NotAPlayer = 16,
+    SessionMismatch = 17,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct TournamentCreated {
+    pub tournament_id: u32,
+    pub organizer: Address,
+    pub game_id: Address,
+}
+
+#[contractevent]
+pub struct EntrantRegistered {
+    pub tournament_id: u32,
+    pub player: Address,
+}
+
+#[contractevent]
+pub struct RoundStarted {
+    pub tournament_id: u32,
+    pub round: u32,
+}
+
+#[contractevent]
+pub struct MatchStarted {
+    pub tournament_id: u32,
+    pub round: u32,
+    pub match_index: u32,
+    pub session_id: u32,
+}
+
+#[contractevent]
+pub struct MatchReported {
+    pub tournament_id: u32,
+    pub round: u32,
+    pub match_index: u32,
+    pub winner: Option<Address>, // None: drawn, or replayed in single elimination
+}
+
+#[contractevent]
+pub struct PrizePaid {
+    pub tournament_id: u32,
+    pub player: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct TournamentFinished {
+    pub tournament_id: u32,
+    pub winner: Address,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    SingleElimination,
+    Swiss(u32), // number of rounds
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Registration,
+    Running,
+    Finished,
+    Cancelled, // called off before the end; fees refunded
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MatchStatus {
+    Pending, // waiting for start_match
+    Started, // hub session running
+    Decided,
+    Drawn,     // Swiss only
+    Forfeited, // Swiss only: nobody played before the deadline; no points
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub player1: Address,
+    pub player2: Option<Address>, // None: player1 has a bye
+    pub session_id: Option<u32>,  // hub session of the current attempt
+    pub attempts: u32,            // session ids used or skipped for this match
+    pub replays: u32,             // elimination sessions that ended without a winner
+    pub deadline_ledger: u32,     // claim_forfeit is allowed after this ledger while Pending
+    pub player1_ready: bool,      // set by check_in
+    pub player2_ready: bool,
+    pub status: MatchStatus,
+    pub winner: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tournament {
+    pub organizer: Address,
+    pub game_id: Address,
+    pub token: Address, // entry fees and prizes are paid in this token
+    pub entry_fee: i128,
+    pub format: Format,
+    pub max_entrants: u32,
+    pub payouts: Vec<u32>, // share of the pool per final place, in basis points
+    pub phase: Phase,
+    pub round: u32, // current round, from 1; 0 before the start
+    pub entrants: Vec<Address>, // in registration order, which is also seeding order
+    pub prize_pool: i128,
+}
+
+/// A player's place in the standings. Points are in half wins: a win or a
+/// bye scores 2, a draw 1.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub player: Address,
+    pub points: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    GameHubAddress,
+    NextId,
+    Tournament(u32),
+    Round(u32, u32), // (tournament_id, round) -> Vec<Match>
+    Points(u32),     // Map<Address, u32> of each entrant's points
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Tournaments can run for weeks, so they live in persistent storage and are
+// bumped whenever they are touched.
+
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const PERSISTENT_TTL_LEDGERS: u32 = 518_400;
+/// Bump once less than a day (17,280 ledgers) is left
+const PERSISTENT_TTL_THRESHOLD: u32 = 17_280;
+
+const MAX_ENTRANTS: u32 = 64;
+const BPS_TOTAL: u32 = 10_000;
+const WIN_POINTS: u32 = 2;
+const DRAW_POINTS: u32 = 1;
+
+/// Time to start a match once its round opens (or it is replayed): 7 days
+const MATCH_LEDGERS: u32 = 120_960;
+/// Elimination sessions without a winner before player1 is sent through
+const MAX_REPLAYS: u32 = 3;
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn read_persistent<T: soroban_sdk::TryFromVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey) -> Option<T> {
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+    }
+    value
+}
+
+fn write_persistent<T: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &DataKey, value: &T) {
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+}
+
+fn load_tournament(env: &Env, tournament_id: u32) -> Result<Tournament, Error> {
+    read_persistent(env, &DataKey::Tournament(tournament_id)).ok_or(Error::TournamentNotFound)
+}
+
+fn load_round(env: &Env, tournament_id: u32, round: u32) -> Vec<Match> {
+    read_persistent(env, &DataKey::Round(tournament_id, round)).unwrap_or_else(|| Vec::new(env))
+}
+
+fn load_points(env: &Env, tournament_id: u32) -> Map<Address, u32> {
+    read_persistent(env, &DataKey::Points(tournament_id)).unwrap_or_else(|| Map::new(env))
+}
+
+fn add_points(points: &mut Map<Address, u32>, player: &Address, amount: u32) {
+    points.set(player.clone(), points.get(player.clone()).unwrap_or(0) + amount);
+}
+
+fn hub(env: &Env) -> GameHubReaderClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .expect("GameHub address not set");
+    GameHubReaderClient::new(env, &address)
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not set")
+}
+
+/// Hub session id for one attempt at a match. Hub ids are global, so this
+/// hashes in the tournament contract's own address as well.
+fn session_id_for(env: &Env, tournament_id: u32, round: u32, match_index: u32, attempt: u32) -> u32 {
+    let mut data = env.current_contract_address().to_xdr(env);
+    for part in [tournament_id, round, match_index, attempt] {
+        data.extend_from_array(&part.to_be_bytes());
+    }
+    let hash = env.crypto().sha256(&data).to_array();
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Entrants by points, best first; equal points keep seeding order.
+fn ranked(env: &Env, entrants: &Vec<Address>, points: &Map<Address, u32>) -> Vec<Entry> {
+    let mut ranking: Vec<Entry> = Vec::new(env);
+    for player in entrants.iter() {
+        let entry = Entry { points: points.get(player.clone()).unwrap_or(0), player };
+        // Insertion sort; fields are small
+        let mut at = ranking.len();
+        while at > 0 && ranking.get_unchecked(at - 1).points < entry.points {
+            at -= 1;
+        }
+        ranking.insert(at, entry);
+    }
+    ranking
+}
+
+fn new_match(player1: Address, player2: Option<Address>) -> Match {
+    Match {
+        player1,
+        player2,
+        session_id: None,
+        attempts: 0,
+        replays: 0,
+        deadline_ledger: 0, // set when the round opens
+        player1_ready: false,
+        player2_ready: false,
+        status: MatchStatus::Pending,
+        winner: None,
+    }
+}
+
+/// First-round bracket: seed 1 meets the lowest seed, and the top two seeds
+/// can only meet in the final. Seeds past the field are byes.
+fn bracket(env: &Env, entrants: &Vec<Address>) -> Vec<Match> {
+    let size = entrants.len().next_power_of_two();
+    // Bracket positions by seed: [0] -> [0, 1] -> [0, 3, 1, 2] -> [0, 7, 3, 4, 1, 6, 2, 5]
+    let mut order: Vec<u32> = Vec::from_array(env, [0]);
+    while order.len() < size {
+        let mut next = Vec::new(env);
+        let mirror = order.len() * 2 - 1;
+        for seed in order.iter() {
+            next.push_back(seed);
+            next.push_back(mirror - seed);
+        }
+        order = next;
+    }
+
+    let mut matches = Vec::new(env);
+    for i in (0..size).step_by(2) {
+        let top = entrants.get_unchecked(order.get_unchecked(i));
+        matches.push_back(new_match(top, entrants.get(order.get_unchecked(i + 1))));
+    }
+    matches
+}
+
+/// Later elimination rounds: winners of neighbouring matches meet.
+fn next_bracket(env: &Env, previous: &Vec<Match>) -> Vec<Match> {
+    let mut matches = Vec::new(env);
+    for i in (0..previous.len()).step_by(2) {
+        let top = previous.get_unchecked(i).winner.unwrap();
+        matches.push_back(new_match(top, previous.get_unchecked(i + 1).winner));
+    }
+    matches
+}
+
+fn have_met(env: &Env, tournament_id: u32, rounds: u32, a: &Address, b: &Address) -> bool {
+    (1..=rounds).any(|round| {
+        load_round(env, tournament_id, round).iter().any(|m| {
+            (m.player1 == *a && m.player2.as_ref() == Some(b)) || (m.player1 == *b && m.player2.as_ref() == Some(a))
+        })
+    })
+}
+
+fn had_bye(env: &Env, tournament_id: u32, rounds: u32, player: &Address) -> bool {
+    (1..=rounds).any(|round| {
+        load_round(env, tournament_id, round)
+            .iter()
+            .any(|m| m.player1 == *player && m.player2.is_none())
+    })
+}
+
+/// Swiss pairings for the next round. With an odd field the lowest-ranked
+/// player without a bye sits out; everyone else is paired top-down with the
+/// best-placed opponent they haven't met, or the next player if they've met
+/// everyone left.
+fn swiss_pairings(env: &Env, tournament_id: u32, tournament: &Tournament) -> Vec<Match> {
+    let played = tournament.round;
+    let mut pool: Vec<Address> = Vec::new(env);
+    for entry in ranked(env, &tournament.entrants, &load_points(env, tournament_id)).iter() {
+        pool.push_back(entry.player);
+    }
+
+    let mut matches = Vec::new(env);
+    let mut bye = None;
+    if pool.len() % 2 == 1 {
+        let index = (0..pool.len())
+            .rev()
+            .find(|&i| !had_bye(env, tournament_id, played, &pool.get_unchecked(i)))
+            .unwrap_or(pool.len() - 1);
+        bye = Some(pool.get_unchecked(index));
+        pool.remove(index);
+    }
+
+    while let Some(player) = pool.pop_front() {
+        let index = (0..pool.len())
+            .find(|&i| !have_met(env, tournament_id, played, &player, &pool.get_unchecked(i)))
+            .unwrap_or(0);
+        let opponent = pool.get_unchecked(index);
+        pool.remove(index);
+        matches.push_back(new_match(player, Some(opponent)));
+    }
+    if let Some(player) = bye {
+        matches.push_back(new_match(player, None));
+    }
+    matches
+}
+
+fn match_deadline(env: &Env) -> u32 {
+    env.ledger().sequence().saturating_add(MATCH_LEDGERS)
+}
+
+/// Send a match back to `Pending` for another attempt, with a new deadline
+/// and fresh check-ins.
+fn replay(env: &Env, m: &mut Match) {
+    m.status = MatchStatus::Pending;
+    m.session_id = None;
+    m.deadline_ledger = match_deadline(env);
+    m.player1_ready = false;
+    m.player2_ready = false;
+}
+
+fn decide(points: &mut Map<Address, u32>, m: &mut Match, winner: Address) {
+    add_points(points, &winner, WIN_POINTS);
+    m.status = MatchStatus::Decided;
+    m.winner = Some(winner);
+}
+
+/// Store a new round, settling its byes straight away.
+fn open_round(env: &Env, tournament_id: u32, tournament: &mut Tournament, mut matches: Vec<Match>) {
+    tournament.round += 1;
+    let mut points = load_points(env, tournament_id);
+    for i in 0..matches.len() {
+        let mut m = matches.get_unchecked(i);
+        m.deadline_ledger = match_deadline(env);
+        if m.player2.is_none() {
+            let winner = m.player1.clone();
+            decide(&mut points, &mut m, winner);
+        }
+        matches.set(i, m);
+    }
+    write_persistent(env, &DataKey::Points(tournament_id), &points);
+    write_persistent(env, &DataKey::Round(tournament_id, tournament.round), &matches);
+    RoundStarted { tournament_id, round: tournament.round }.publish(env);
+}
+
+/// Once every match of the current round is settled, pair the next round or
+/// finish the tournament, and store it.
+fn close_round_if_settled(env: &Env, tournament_id: u32, tournament: &mut Tournament, matches: &Vec<Match>) {
+    let settled = matches
+        .iter()
+        .all(|m| matches!(m.status, MatchStatus::Decided | MatchStatus::Drawn | MatchStatus::Forfeited));
+    if !settled {
+        return;
+    }
+    match tournament.format {
+        Format::SingleElimination if matches.len() == 1 => finish(env, tournament_id, tournament),
+        Format::SingleElimination => {
+            let next = next_bracket(env, matches);
+            open_round(env, tournament_id, tournament, next);
+        }
+        Format::Swiss(rounds) if tournament.round == rounds => finish(env, tournament_id, tournament),
+        Format::Swiss(_) => {
+            let next = swiss_pairings(env, tournament_id, tournament);
+            open_round(env, tournament_id, tournament, next);
+        }
+    }
+    write_persistent(env, &DataKey::Tournament(tournament_id), tournament);
+}
+
+/// Send every entry fee back; the pool is untouched until `finish`.
+fn refund_entrants(env: &Env, tournament: &mut Tournament) {
+    if tournament.entry_fee > 0 {
+        let token = token::Client::new(env, &tournament.token);
+        for player in tournament.entrants.iter() {
+            token.transfer(&env.current_contract_address(), &player, &tournament.entry_fee);
+        }
+    }
+    tournament.prize_pool = 0;
+    tournament.phase = Phase::Cancelled;
+}
+
+/// Pay the prize pool by final standing and close the tournament. Rounding
+/// dust, and shares for places nobody finished in, go to the winner.
+fn finish(env: &Env, tournament_id: u32, tournament: &mut Tournament) {
+    let standings = ranked(env, &tournament.entrants, &load_points(env, tournament_id));
+    let places = tournament.payouts.len().min(standings.len());
+
+    let mut prizes: Vec<i128> = Vec::new(env);
+    let mut paid = 0;
+    for place in 0..places {
+        let share = tournament.prize_pool * tournament.payouts.get_unchecked(place) as i128 / BPS_TOTAL as i128;
+        prizes.push_back(share);
+        paid += share;
+    }
+    prizes.set(0, prizes.get_unchecked(0) + tournament.prize_pool - paid);
+
+    let token = token::Client::new(env, &tournament.token);
+    for place in 0..places {
+        let (player, amount) = (standings.get_unchecked(place).player, prizes.get_unchecked(place));
+        if amount > 0 {
+            token.transfer(&env.current_contract_address(), &player, &amount);
+            PrizePaid { tournament_id, player, amount }.publish(env);
+        }
+    }
+
+    tournament.phase = Phase::Finished;
+    TournamentFinished {
+        tournament_id,
+        winner: standings.get_unchecked(0).player,
+    }
+    .publish(env);
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct TournamentContract;
+
+#[contractimpl]
+impl TournamentContract {
+    /// Initialize the contract with GameHub address and admin
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `game_hub` - Address of the GameHub contract
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::GameHubAddress, &game_hub);
+    }
+
+    /// Create a tournament and open registration.
+    ///
+    /// # Arguments
+    /// * `organizer` - Starts the tournament; may cancel it before then
+    /// * `game_id` - Game contract the matches are played in; must be active on the hub
+    /// * `token` - Token the entry fee is paid in, and the prizes
+    /// * `entry_fee` - Fee per entrant (0 for a free tournament)
+    /// * `format` - `SingleElimination` or `Swiss(rounds)`
+    /// * `max_entrants` - Field size limit, 2 to 64
+    /// * `payouts` - Share of the pool for 1st, 2nd, … place in basis points, summing to 10,000
+    ///
+    /// # Returns
+    /// * `u32` - The new tournament's id
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tournament(
+        env: Env,
+        organizer: Address,
+        game_id: Address,
+        token: Address,
+        entry_fee: i128,
+        format: Format,
+        max_entrants: u32,
+        payouts: Vec<u32>,
+    ) -> Result<u32, Error> {
+        organizer.require_auth();
+
+        if !hub(&env).is_game_active(&game_id) {
+            return Err(Error::GameNotRegistered);
+        }
+        if entry_fee < 0 {
+            return Err(Error::InvalidEntryFee);
+        }
+        if !(2..=MAX_ENTRANTS).contains(&max_entrants) || format == Format::Swiss(0) {
+            return Err(Error::InvalidFormat);
+        }
+        if payouts.is_empty()
+            || payouts.len() > max_entrants
+            || payouts.iter().any(|share| share == 0 || share > BPS_TOTAL)
+            || payouts.iter().sum::<u32>() != BPS_TOTAL
+        {
+            return Err(Error::InvalidPayouts);
+        }
+
+        let tournament_id: u32 = env.storage().instance().get(&DataKey::NextId).unwrap_or(1);
+        env.storage().instance().set(&DataKey::NextId, &(tournament_id + 1));
+
+        let tournament = Tournament {
+            organizer: organizer.clone(),
+            game_id: game_id.clone(),
+            token,
+            entry_fee,
+            format,
+            max_entrants,
+            payouts,
+            phase: Phase::Registration,
+            round: 0,
+            entrants: Vec::new(&env),
+            prize_pool: 0,
+        };
+        write_persistent(&env, &DataKey::Tournament(tournament_id), &tournament);
+        TournamentCreated {
+            tournament_id,
+            organizer,
+            game_id,
+        }
+        .publish(&env);
+        env.storage().instance().extend_ttl(PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_LEDGERS);
+        Ok(tournament_id)
+    }
+
+    /// Enter a tournament, paying the entry fee into the prize pool.
+    pub fn register(env: Env, tournament_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if tournament.phase != Phase::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+        if tournament.entrants.len() >= tournament.max_entrants {
+            return Err(Error::TournamentFull);
+        }
+        if tournament.entrants.contains(&player) {
+            return Err(Error::AlreadyRegistered);
+        }
+
+        if tournament.entry_fee > 0 {
+            token::Client::new(&env, &tournament.token).transfer(
+                &player,
+                env.current_contract_address(),
+                &tournament.entry_fee,
+            );
+            tournament.prize_pool += tournament.entry_fee;
+        }
+        tournament.entrants.push_back(player.clone());
+        write_persistent(&env, &DataKey::Tournament(tournament_id), &tournament);
+        EntrantRegistered { tournament_id, player }.publish(&env);
+        Ok(())
+    }
+
+    /// Call a tournament off before it starts and refund every entry fee.
+    /// Organizer only.
+    pub fn cancel(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        tournament.organizer.require_auth();
+        if tournament.phase != Phase::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+
+        refund_entrants(&env, &mut tournament);
+        write_persistent(&env, &DataKey::Tournament(tournament_id), &tournament);
+        Ok(())
+    }
+
+    /// Close registration and pair the first round. Organizer only.
+    pub fn start(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        tournament.organizer.require_auth();
+        if tournament.phase != Phase::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+        if tournament.entrants.len() < 2 {
+            return Err(Error::NotEnoughEntrants);
+        }
+
+        tournament.phase = Phase::Running;
+        let matches = match tournament.format {
+            Format::SingleElimination => bracket(&env, &tournament.entrants),
+            Format::Swiss(_) => swiss_pairings(&env, tournament_id, &tournament),
+        };
+        open_round(&env, tournament_id, &mut tournament, matches);
+        write_persistent(&env, &DataKey::Tournament(tournament_id), &tournament);
+        Ok(())
+    }
+
+    /// Start a match of the current round as a session in the tournament's
+    /// game. Both players authorize it, which also covers the game's own
+    /// `require_auth` calls for the session.
+    ///
+    /// # Returns
+    /// * `u32` - The hub session id the match is played under
+    pub fn start_match(env: Env, tournament_id: u32, match_index: u32) -> Result<u32, Error> {
+        let tournament = load_tournament(&env, tournament_id)?;
+        if tournament.phase != Phase::Running {
+            return Err(Error::NotRunning);
+        }
+        let key = DataKey::Round(tournament_id, tournament.round);
+        let mut matches = load_round(&env, tournament_id, tournament.round);
+        let mut m = matches.get(match_index).ok_or(Error::MatchNotFound)?;
+        let Some(player2) = m.player2.clone().filter(|_| m.status == MatchStatus::Pending) else {
+            return Err(Error::MatchNotPending);
+        };
+        m.player1.require_auth();
+        player2.require_auth();
+
+        // Hub ids are global and these are predictable, so skip any id a
+        // session already holds rather than fail in the game's start_game
+        let hub = hub(&env);
+        let mut session_id = session_id_for(&env, tournament_id, tournament.round, match_index, m.attempts);
+        while hub.try_get_session(&session_id).is_ok() {
+            m.attempts += 1;
+            session_id = session_id_for(&env, tournament_id, tournament.round, match_index, m.attempts);
+        }
+        GameClient::new(&env, &tournament.game_id).start_game(&session_id, &m.player1, &player2, &0, &0);
+
+        m.session_id = Some(session_id);
+        m.attempts += 1;
+        m.status = MatchStatus::Started;
+        matches.set(match_index, m);
+        write_persistent(&env, &key, &matches);
+        MatchStarted {
+            tournament_id,
+            round: tournament.round,
+            match_index,
+            session_id,
+        }
+        .publish(&env);
+        Ok(session_id)
+    }
+
+    /// Record a finished match from its hub session. Anyone may call this.
+    /// The session must be this match's game and players. When it completes
+    /// the round, the next round is paired, or the tournament finishes and
+    /// pays out.
+    pub fn report_result(env: Env, tournament_id: u32, match_index: u32) -> Result<(), Error> {
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if tournament.phase != Phase::Running {
+            return Err(Error::NotRunning);
+        }
+        let round = tournament.round;
+        let mut matches = load_round(&env, tournament_id, round);
+        let mut m = matches.get(match_index).ok_or(Error::MatchNotFound)?;
+        let Some(session_id) = m.session_id.filter(|_| m.status == MatchStatus::Started) else {
+            return Err(Error::MatchNotStarted);
+        };
+
+        let session = hub(&env).get_session(&session_id);
+        if session.game_id != tournament.game_id
+            || session.player1 != m.player1
+            || Some(&session.player2) != m.player2.as_ref()
+        {
+            return Err(Error::SessionMismatch);
+        }
+        if session.status == SessionStatus::Active {
+            return Err(Error::MatchInProgress);
+        }
+
+        let mut points = load_points(&env, tournament_id);
+        match (&session.winner, tournament.format) {
+            (Some(winner), _) => decide(&mut points, &mut m, winner.clone()),
+            (None, Format::Swiss(_)) => {
+                add_points(&mut points, &session.player1, DRAW_POINTS);
+                add_points(&mut points, &session.player2, DRAW_POINTS);
+                m.status = MatchStatus::Drawn;
+            }
+            // An elimination match needs a winner: play it again, up to a point
+            (None, Format::SingleElimination) => {
+                m.replays += 1;
+                if m.replays >= MAX_REPLAYS {
+                    let winner = m.player1.clone();
+                    decide(&mut points, &mut m, winner);
+                } else {
+                    replay(&env, &mut m);
+                }
+            }
+        }
+        MatchReported {
+            tournament_id,
+            round,
+            match_index,
+            winner: m.winner.clone(),
+        }
+        .publish(&env);
+        matches.set(match_index, m);
+        write_persistent(&env, &DataKey::Round(tournament_id, round), &matches);
+        write_persistent(&env, &DataKey::Points(tournament_id), &points);
+
+        close_round_if_settled(&env, tournament_id, &mut tournament, &matches);
+        Ok(())
+    }
+
+    /// Say you're ready to play a pending match of the current round. Counts
+    /// for `claim_forfeit` if the match isn't started by its deadline.
+    pub fn check_in(env: Env, tournament_id: u32, match_index: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let tournament = load_tournament(&env, tournament_id)?;
+        if tournament.phase != Phase::Running {
+            return Err(Error::NotRunning);
+        }
+        let key = DataKey::Round(tournament_id, tournament.round);
+        let mut matches = load_round(&env, tournament_id, tournament.round);
+        let mut m = matches.get(match_index).ok_or(Error::MatchNotFound)?;
+        if m.status != MatchStatus::Pending {
+            return Err(Error::MatchNotPending);
+        }
+        if player == m.player1 {
+            m.player1_ready = true;
+        } else if Some(&player) == m.player2.as_ref() {
+            m.player2_ready = true;
+        } else {
+            return Err(Error::NotAPlayer);
+        }
+        matches.set(match_index, m);
+        write_persistent(&env, &key, &matches);
+        Ok(())
+    }
+
+    /// Settle a match that wasn't started by its deadline. Anyone may call
+    /// this. A player who checked in wins against one who didn't. If both
+    /// checked in, neither can be blamed and the match is replayed with a new
+    /// deadline. If neither did, a Swiss match is forfeited with no points,
+    /// and in elimination player1 goes through so the bracket can go on.
+    pub fn claim_forfeit(env: Env, tournament_id: u32, match_index: u32) -> Result<(), Error> {
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if tournament.phase != Phase::Running {
+            return Err(Error::NotRunning);
+        }
+        let round = tournament.round;
+        let mut matches = load_round(&env, tournament_id, round);
+        let mut m = matches.get(match_index).ok_or(Error::MatchNotFound)?;
+        if m.status != MatchStatus::Pending {
+            return Err(Error::MatchNotPending);
+        }
+        if env.ledger().sequence() <= m.deadline_ledger {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        let mut points = load_points(&env, tournament_id);
+        match (m.player1_ready, m.player2_ready, tournament.format) {
+            (true, true, _) => replay(&env, &mut m),
+            (false, true, _) => {
+                let winner = m.player2.clone().unwrap();
+                decide(&mut points, &mut m, winner);
+            }
+            (true, false, _) | (false, false, Format::SingleElimination) => {
+                let winner = m.player1.clone();
+                decide(&mut points, &mut m, winner);
+            }
+            (false, false, Format::Swiss(_)) => m.status = MatchStatus::Forfeited,
+        }
+        MatchReported {
+            tournament_id,
+            round,
+            match_index,
+            winner: m.winner.clone(),
+        }
+        .publish(&env);
+        matches.set(match_index, m);
+        write_persistent(&env, &DataKey::Round(tournament_id, round), &matches);
+        write_persistent(&env, &DataKey::Points(tournament_id), &points);
+
+        close_round_if_settled(&env, tournament_id, &mut tournament, &matches);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Queries
+    // ========================================================================
+
+    /// Get a tournament's settings, entrants and progress.
+    pub fn get_tournament(env: Env, tournament_id: u32) -> Result<Tournament, Error> {
+        load_tournament(&env, tournament_id)
+    }
+
+    /// The matches of one round (from 1), in bracket order.
+    pub fn get_round(env: Env, tournament_id: u32, round: u32) -> Result<Vec<Match>, Error> {
+        load_tournament(&env, tournament_id)?;
+        Ok(load_round(&env, tournament_id, round))
+    }
+
+    /// Entrants by points, best first; ties keep seeding order. After the
+    /// final round this is the order prizes are paid in.
+    pub fn get_standings(env: Env, tournament_id: u32) -> Result<Vec<Entry>, Error> {
+        let tournament = load_tournament(&env, tournament_id)?;
+        Ok(ranked(&env, &tournament.entrants, &load_points(&env, tournament_id)))
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Call off a tournament that hasn't finished, even mid-run, and refund
+    /// every entry fee. Admin only; for tournaments that can't go on.
+    /// Sessions already started at the hub are left to end or expire there.
+    pub fn abort(env: Env, tournament_id: u32) -> Result<(), Error> {
+        admin(&env).require_auth();
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if !matches!(tournament.phase, Phase::Registration | Phase::Running) {
+            return Err(Error::NotRunning);
+        }
+        refund_entrants(&env, &mut tournament);
+        write_persistent(&env, &DataKey::Tournament(tournament_id), &tournament);
+        Ok(())
+    }
+
+    /// Get the current admin address
+    ///
+    /// # Returns
+    /// * `Address` - The admin address
+    pub fn get_admin(env: Env) -> Address {
+        admin(&env)
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) {
+        admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    /// Get the current GameHub contract address
+    ///
+    /// # Returns
+    /// * `Address` - The GameHub contract address
+    pub fn get_hub(env: Env) -> Address {
+        hub(&env).address
+    }
+
+    /// Set a new GameHub contract address
+    ///
+    /// # Arguments
+    /// * `new_hub` - The new GameHub contract address
+    pub fn set_hub(env: Env, new_hub: Address) {
+        admin(&env).require_auth();
+        env.storage().instance().set(&DataKey::GameHubAddress, &new_hub);
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        admin(&env).require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
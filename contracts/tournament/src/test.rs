@@ -0,0 +1,659 @@
+#![cfg(test)]
+
+// Tournament tests against the real Game Hub. Matches are played in a small
+// test game whose results the tests choose, so brackets come out as planned.
+
+use crate::{session_id_for, write_persistent, DataKey, MATCH_LEDGERS, MAX_REPLAYS};
+use crate::{Error, Format, MatchStatus, Phase, TournamentContract, TournamentContractClient};
+use game_hub::{GameHub, GameHubClient};
+use game_hub_client::Outcome;
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, String, Vec};
+
+const ENTRY_FEE: i128 = 100;
+const STARTING_TOKENS: i128 = 1_000;
+
+// ============================================================================
+// Test Game
+// ============================================================================
+
+#[contracttype]
+pub enum TestGameKey {
+    Hub,
+}
+
+/// A game with the standard `start_game`, ended by whatever outcome the test
+/// reports through `finish`.
+#[contract]
+pub struct TestGame;
+
+#[contractimpl]
+impl TestGame {
+    pub fn __constructor(env: Env, hub: Address) {
+        env.storage().instance().set(&TestGameKey::Hub, &hub);
+    }
+
+    pub fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) {
+        player1.require_auth();
+        player2.require_auth();
+        hub_client(&env).start_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+    }
+
+    pub fn finish(env: Env, session_id: u32, outcome: Outcome) {
+        hub_client(&env).end_game_with_outcome(&session_id, &outcome);
+    }
+}
+
+fn hub_client(env: &Env) -> GameHubClient<'_> {
+    let hub: Address = env.storage().instance().get(&TestGameKey::Hub).unwrap();
+    GameHubClient::new(env, &hub)
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+struct Setup {
+    env: Env,
+    tournament: TournamentContractClient<'static>,
+    game: TestGameClient<'static>,
+    token: TokenClient<'static>,
+    admin: Address,
+    organizer: Address,
+    players: Vec<Address>,
+}
+
+fn setup_test(player_count: u32) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(GameHub, (&admin,));
+    let hub = GameHubClient::new(&env, &hub_addr);
+
+    let game_addr = env.register(TestGame, (&hub_addr,));
+    hub.add_game(&game_addr, &String::from_str(&env, "Test Game"), &1);
+
+    let tournament_addr = env.register(TournamentContract, (&admin, &hub_addr));
+
+    let token_addr = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let minter = StellarAssetClient::new(&env, &token_addr);
+    let mut players = Vec::new(&env);
+    for _ in 0..player_count {
+        let player = Address::generate(&env);
+        minter.mint(&player, &STARTING_TOKENS);
+        players.push_back(player);
+    }
+
+    Setup {
+        tournament: TournamentContractClient::new(&env, &tournament_addr),
+        game: TestGameClient::new(&env, &game_addr),
+        token: TokenClient::new(&env, &token_addr),
+        admin,
+        organizer: Address::generate(&env),
+        players,
+        env,
+    }
+}
+
+impl Setup {
+    fn player(&self, seed: u32) -> Address {
+        self.players.get(seed).unwrap()
+    }
+
+    /// Create a tournament, register every player and start it.
+    fn run(&self, format: Format, payouts: Vec<u32>) -> u32 {
+        let id = self.create(format, payouts);
+        for player in self.players.iter() {
+            self.tournament.register(&id, &player);
+        }
+        self.tournament.start(&id);
+        id
+    }
+
+    fn create(&self, format: Format, payouts: Vec<u32>) -> u32 {
+        self.tournament.create_tournament(
+            &self.organizer,
+            &self.game.address,
+            &self.token.address,
+            &ENTRY_FEE,
+            &format,
+            &(self.players.len().max(2)),
+            &payouts,
+        )
+    }
+
+    /// Play match `index` of the current round and report it.
+    fn play(&self, id: u32, index: u32, outcome: Outcome) {
+        let session_id = self.tournament.start_match(&id, &index);
+        self.game.finish(&session_id, &outcome);
+        self.tournament.report_result(&id, &index);
+    }
+
+    /// Move the ledger past the deadline of every match opened so far.
+    fn pass_match_deadline(&self) {
+        self.env.ledger().with_mut(|ledger| ledger.sequence_number += MATCH_LEDGERS + 1);
+    }
+
+    /// Open a hub session in the test game between two outsiders.
+    fn squat_session(&self, session_id: u32) {
+        let (a, b) = (Address::generate(&self.env), Address::generate(&self.env));
+        self.game.start_game(&session_id, &a, &b, &0, &0);
+    }
+}
+
+/// Assert that a Result contains a specific tournament error
+///
+/// The try_ methods return: `Result<Result<T, T::Error>, Result<E, InvokeError>>`
+/// - Err(Ok(error)): Contract reverted with custom error (THIS IS WHAT WE TEST)
+fn assert_tournament_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Registration Tests
+// ============================================================================
+
+#[test]
+fn test_register_collects_entry_fees() {
+    let s = setup_test(3);
+    let id = s.create(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    for player in s.players.iter() {
+        s.tournament.register(&id, &player);
+    }
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.entrants, s.players);
+    assert_eq!(tournament.prize_pool, 3 * ENTRY_FEE);
+    assert_eq!(tournament.phase, Phase::Registration);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS - ENTRY_FEE);
+    assert_eq!(s.token.balance(&s.tournament.address), 3 * ENTRY_FEE);
+}
+
+#[test]
+fn test_register_rejects_duplicates_and_full_fields() {
+    let s = setup_test(3);
+    let id = s.tournament.create_tournament(
+        &s.organizer,
+        &s.game.address,
+        &s.token.address,
+        &ENTRY_FEE,
+        &Format::SingleElimination,
+        &2,
+        &vec![&s.env, 10_000],
+    );
+
+    s.tournament.register(&id, &s.player(0));
+    assert_tournament_error(&s.tournament.try_register(&id, &s.player(0)), Error::AlreadyRegistered);
+    s.tournament.register(&id, &s.player(1));
+    assert_tournament_error(&s.tournament.try_register(&id, &s.player(2)), Error::TournamentFull);
+}
+
+#[test]
+fn test_register_closes_at_the_start() {
+    let s = setup_test(3);
+    let id = s.create(Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournament.register(&id, &s.player(0));
+    s.tournament.register(&id, &s.player(1));
+    s.tournament.start(&id);
+
+    assert_tournament_error(&s.tournament.try_register(&id, &s.player(2)), Error::RegistrationClosed);
+    assert_tournament_error(&s.tournament.try_start(&id), Error::RegistrationClosed);
+    assert_tournament_error(&s.tournament.try_cancel(&id), Error::RegistrationClosed);
+}
+
+#[test]
+fn test_cancel_refunds_entry_fees() {
+    let s = setup_test(2);
+    let id = s.create(Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournament.register(&id, &s.player(0));
+    s.tournament.register(&id, &s.player(1));
+
+    s.tournament.cancel(&id);
+
+    assert_eq!(s.env.auths()[0].0, s.organizer);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Cancelled);
+}
+
+#[test]
+fn test_start_needs_two_entrants() {
+    let s = setup_test(1);
+    let id = s.create(Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournament.register(&id, &s.player(0));
+
+    assert_tournament_error(&s.tournament.try_start(&id), Error::NotEnoughEntrants);
+}
+
+#[test]
+fn test_create_validates_settings() {
+    let s = setup_test(2);
+    let create = |game: &Address, fee: i128, format: Format, max: u32, payouts: Vec<u32>| {
+        s.tournament
+            .try_create_tournament(&s.organizer, game, &s.token.address, &fee, &format, &max, &payouts)
+    };
+    let all = vec![&s.env, 10_000];
+
+    let stranger = Address::generate(&s.env);
+    assert_tournament_error(&create(&stranger, 0, Format::SingleElimination, 8, all.clone()), Error::GameNotRegistered);
+    assert_tournament_error(&create(&s.game.address, -1, Format::SingleElimination, 8, all.clone()), Error::InvalidEntryFee);
+    assert_tournament_error(&create(&s.game.address, 0, Format::Swiss(0), 8, all.clone()), Error::InvalidFormat);
+    assert_tournament_error(&create(&s.game.address, 0, Format::SingleElimination, 1, all.clone()), Error::InvalidFormat);
+    assert_tournament_error(&create(&s.game.address, 0, Format::SingleElimination, 65, all), Error::InvalidFormat);
+    assert_tournament_error(
+        &create(&s.game.address, 0, Format::SingleElimination, 8, vec![&s.env, 6_000, 3_000]),
+        Error::InvalidPayouts,
+    );
+    assert_tournament_error(
+        &create(&s.game.address, 0, Format::SingleElimination, 2, vec![&s.env, 5_000, 3_000, 2_000]),
+        Error::InvalidPayouts,
+    );
+    assert_tournament_error(
+        &create(&s.game.address, 0, Format::SingleElimination, 8, Vec::new(&s.env)),
+        Error::InvalidPayouts,
+    );
+}
+
+// ============================================================================
+// Single Elimination Tests
+// ============================================================================
+
+#[test]
+fn test_single_elimination_runs_to_a_champion() {
+    let s = setup_test(4);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 7_000, 3_000]);
+
+    // Seed 1 meets seed 4 and seed 2 meets seed 3
+    let round1 = s.tournament.get_round(&id, &1);
+    assert_eq!(round1.get(0).unwrap().player1, s.player(0));
+    assert_eq!(round1.get(0).unwrap().player2, Some(s.player(3)));
+    assert_eq!(round1.get(1).unwrap().player1, s.player(1));
+    assert_eq!(round1.get(1).unwrap().player2, Some(s.player(2)));
+
+    s.play(id, 0, Outcome::Player1Won);
+    assert_eq!(s.tournament.get_tournament(&id).round, 1);
+    s.play(id, 1, Outcome::Player2Won);
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.round, 2);
+    let final_match = s.tournament.get_round(&id, &2).get(0).unwrap();
+    assert_eq!(final_match.player1, s.player(0));
+    assert_eq!(final_match.player2, Some(s.player(2)));
+
+    s.play(id, 0, Outcome::Player2Won);
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.phase, Phase::Finished);
+    let standings = s.tournament.get_standings(&id);
+    assert_eq!(standings.get(0).unwrap().player, s.player(2));
+    assert_eq!(standings.get(1).unwrap().player, s.player(0));
+
+    // 400 in the pool: 70% and 30%
+    assert_eq!(s.token.balance(&s.player(2)), STARTING_TOKENS - ENTRY_FEE + 280);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS - ENTRY_FEE + 120);
+    assert_eq!(s.token.balance(&s.player(1)), STARTING_TOKENS - ENTRY_FEE);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+}
+
+#[test]
+fn test_top_seeds_get_byes() {
+    let s = setup_test(3);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    let round1 = s.tournament.get_round(&id, &1);
+    let bye = round1.get(0).unwrap();
+    assert_eq!(bye.player1, s.player(0));
+    assert_eq!(bye.player2, None);
+    assert_eq!(bye.status, MatchStatus::Decided);
+    assert_tournament_error(&s.tournament.try_start_match(&id, &0), Error::MatchNotPending);
+
+    s.play(id, 1, Outcome::Player1Won);
+    let final_match = s.tournament.get_round(&id, &2).get(0).unwrap();
+    assert_eq!(final_match.player1, s.player(0));
+    assert_eq!(final_match.player2, Some(s.player(1)));
+}
+
+#[test]
+fn test_drawn_elimination_match_is_replayed() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    let first = s.tournament.start_match(&id, &0);
+    s.game.finish(&first, &Outcome::Draw);
+    s.tournament.report_result(&id, &0);
+
+    let replay = s.tournament.get_round(&id, &1).get(0).unwrap();
+    assert_eq!(replay.status, MatchStatus::Pending);
+    assert_eq!(replay.attempts, 1);
+
+    let second = s.tournament.start_match(&id, &0);
+    assert_ne!(first, second);
+    s.game.finish(&second, &Outcome::Player2Won);
+    s.tournament.report_result(&id, &0);
+
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+    assert_eq!(s.token.balance(&s.player(1)), STARTING_TOKENS + ENTRY_FEE);
+}
+
+// ============================================================================
+// Swiss Tests
+// ============================================================================
+
+#[test]
+fn test_swiss_pairs_by_score_without_rematches() {
+    let s = setup_test(4);
+    let id = s.run(Format::Swiss(2), vec![&s.env, 10_000]);
+
+    s.play(id, 0, Outcome::Player1Won); // seed 1 beats seed 2
+    s.play(id, 1, Outcome::Draw); // seeds 3 and 4 draw
+
+    // Seed 1 leads; seeds 3 and 4 share second, and seed 3 hasn't met seed 1
+    let round2 = s.tournament.get_round(&id, &2);
+    assert_eq!(round2.get(0).unwrap().player1, s.player(0));
+    assert_eq!(round2.get(0).unwrap().player2, Some(s.player(2)));
+    assert_eq!(round2.get(1).unwrap().player1, s.player(3));
+    assert_eq!(round2.get(1).unwrap().player2, Some(s.player(1)));
+
+    s.play(id, 0, Outcome::Player1Won);
+    s.play(id, 1, Outcome::Draw);
+
+    let standings = s.tournament.get_standings(&id);
+    assert_eq!(standings.get(0).unwrap().player, s.player(0));
+    assert_eq!(standings.get(0).unwrap().points, 4);
+    assert_eq!(standings.get(1).unwrap().points, 2); // seed 4: two draws
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS + 3 * ENTRY_FEE);
+}
+
+#[test]
+fn test_swiss_gives_each_bye_once() {
+    let s = setup_test(3);
+    let id = s.run(Format::Swiss(2), vec![&s.env, 10_000]);
+
+    let round1 = s.tournament.get_round(&id, &1);
+    assert_eq!(round1.get(1).unwrap().player1, s.player(2));
+    assert_eq!(round1.get(1).unwrap().player2, None);
+
+    s.play(id, 0, Outcome::Player1Won);
+
+    let round2 = s.tournament.get_round(&id, &2);
+    assert_eq!(round2.get(0).unwrap().player1, s.player(0));
+    assert_eq!(round2.get(0).unwrap().player2, Some(s.player(2)));
+    assert_eq!(round2.get(1).unwrap().player1, s.player(1));
+    assert_eq!(round2.get(1).unwrap().player2, None);
+}
+
+#[test]
+fn test_rounding_dust_goes_to_the_winner() {
+    let s = setup_test(3);
+    let id = s.run(Format::Swiss(1), vec![&s.env, 3_333, 3_333, 3_334]);
+
+    s.play(id, 0, Outcome::Player1Won);
+
+    // 300 in the pool: 99 + 99 + 100, plus 2 left over for first place
+    // (seed 1 and the bye both have a win; seed 1 ranks first)
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS - ENTRY_FEE + 101);
+    assert_eq!(s.token.balance(&s.player(2)), STARTING_TOKENS - ENTRY_FEE + 99);
+    assert_eq!(s.token.balance(&s.player(1)), STARTING_TOKENS - ENTRY_FEE + 100);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+}
+
+// ============================================================================
+// Match Flow Tests
+// ============================================================================
+
+#[test]
+fn test_start_match_needs_both_players() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    s.tournament.start_match(&id, &0);
+
+    let authorized: Vec<Address> = Vec::from_iter(&s.env, s.env.auths().into_iter().map(|(address, _)| address));
+    assert!(authorized.contains(s.player(0)));
+    assert!(authorized.contains(s.player(1)));
+}
+
+#[test]
+fn test_report_requires_a_finished_session() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    assert_tournament_error(&s.tournament.try_report_result(&id, &0), Error::MatchNotStarted);
+    s.tournament.start_match(&id, &0);
+    assert_tournament_error(&s.tournament.try_report_result(&id, &0), Error::MatchInProgress);
+    assert_tournament_error(&s.tournament.try_start_match(&id, &0), Error::MatchNotPending);
+    assert_tournament_error(&s.tournament.try_start_match(&id, &1), Error::MatchNotFound);
+}
+
+#[test]
+fn test_matches_need_a_running_tournament() {
+    let s = setup_test(2);
+    let id = s.create(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    assert_tournament_error(&s.tournament.try_start_match(&id, &0), Error::NotRunning);
+    assert_tournament_error(&s.tournament.try_report_result(&id, &0), Error::NotRunning);
+    assert_tournament_error(&s.tournament.try_get_tournament(&99), Error::TournamentNotFound);
+}
+
+#[test]
+fn test_start_match_skips_taken_session_ids() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    // Someone opens a hub session under the id the first attempt would use
+    let taken = s.env.as_contract(&s.tournament.address, || session_id_for(&s.env, id, 1, 0, 0));
+    s.squat_session(taken);
+
+    let session_id = s.tournament.start_match(&id, &0);
+    assert_ne!(session_id, taken);
+    assert_eq!(s.tournament.get_round(&id, &1).get(0).unwrap().attempts, 2);
+
+    s.game.finish(&session_id, &Outcome::Player2Won);
+    s.tournament.report_result(&id, &0);
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+}
+
+#[test]
+fn test_report_result_checks_the_session() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournament.start_match(&id, &0);
+
+    // Point the match at a session between other players
+    s.squat_session(77);
+    s.game.finish(&77, &Outcome::Player1Won);
+    let mut round = s.tournament.get_round(&id, &1);
+    let mut m = round.get(0).unwrap();
+    m.session_id = Some(77);
+    round.set(0, m);
+    s.env.as_contract(&s.tournament.address, || write_persistent(&s.env, &DataKey::Round(id, 1), &round));
+
+    assert_tournament_error(&s.tournament.try_report_result(&id, &0), Error::SessionMismatch);
+}
+
+#[test]
+fn test_elimination_replays_are_capped() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    for _ in 0..MAX_REPLAYS {
+        s.play(id, 0, Outcome::Draw);
+    }
+
+    // No winner after every replay: player1 goes through and takes the final
+    let last = s.tournament.get_round(&id, &1).get(0).unwrap();
+    assert_eq!(last.replays, MAX_REPLAYS);
+    assert_eq!(last.winner, Some(s.player(0)));
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS + ENTRY_FEE);
+}
+
+// ============================================================================
+// Deadline Tests
+// ============================================================================
+
+#[test]
+fn test_no_show_forfeits_to_the_player_who_checked_in() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    let outsider = Address::generate(&s.env);
+    assert_tournament_error(&s.tournament.try_check_in(&id, &0, &outsider), Error::NotAPlayer);
+    s.tournament.check_in(&id, &0, &s.player(1));
+    assert_tournament_error(&s.tournament.try_claim_forfeit(&id, &0), Error::DeadlineNotReached);
+
+    s.pass_match_deadline();
+    s.tournament.claim_forfeit(&id, &0);
+
+    assert_eq!(s.tournament.get_round(&id, &1).get(0).unwrap().winner, Some(s.player(1)));
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+    assert_eq!(s.token.balance(&s.player(1)), STARTING_TOKENS + ENTRY_FEE);
+}
+
+#[test]
+fn test_both_checked_in_replays_the_match() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    // Both turned up, so nobody can be blamed for the match not starting
+    s.tournament.check_in(&id, &0, &s.player(0));
+    s.tournament.check_in(&id, &0, &s.player(1));
+    s.pass_match_deadline();
+    s.tournament.claim_forfeit(&id, &0);
+
+    let m = s.tournament.get_round(&id, &1).get(0).unwrap();
+    assert_eq!(m.status, MatchStatus::Pending);
+    assert_eq!(m.winner, None);
+    assert!(!m.player1_ready && !m.player2_ready);
+    assert!(m.deadline_ledger > s.env.ledger().sequence());
+    assert_tournament_error(&s.tournament.try_claim_forfeit(&id, &0), Error::DeadlineNotReached);
+
+    // Nobody turned up to the replay: the bracket goes on with player1
+    s.pass_match_deadline();
+    s.tournament.claim_forfeit(&id, &0);
+    assert_eq!(s.tournament.get_round(&id, &1).get(0).unwrap().winner, Some(s.player(0)));
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+}
+
+#[test]
+fn test_replayed_match_needs_fresh_check_ins() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    // A check-in for the drawn attempt doesn't carry over to the replay
+    s.tournament.check_in(&id, &0, &s.player(0));
+    s.play(id, 0, Outcome::Draw);
+    let m = s.tournament.get_round(&id, &1).get(0).unwrap();
+    assert!(!m.player1_ready && !m.player2_ready);
+
+    s.tournament.check_in(&id, &0, &s.player(1));
+    s.pass_match_deadline();
+    s.tournament.claim_forfeit(&id, &0);
+    assert_eq!(s.tournament.get_round(&id, &1).get(0).unwrap().winner, Some(s.player(1)));
+}
+
+#[test]
+fn test_started_match_cannot_be_forfeited() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+
+    s.tournament.start_match(&id, &0);
+    s.pass_match_deadline();
+    assert_tournament_error(&s.tournament.try_claim_forfeit(&id, &0), Error::MatchNotPending);
+    assert_tournament_error(&s.tournament.try_check_in(&id, &0, &s.player(0)), Error::MatchNotPending);
+}
+
+#[test]
+fn test_swiss_double_no_show_scores_nothing() {
+    let s = setup_test(2);
+    let id = s.run(Format::Swiss(1), vec![&s.env, 10_000]);
+
+    s.pass_match_deadline();
+    s.tournament.claim_forfeit(&id, &0);
+
+    assert_eq!(s.tournament.get_round(&id, &1).get(0).unwrap().status, MatchStatus::Forfeited);
+    let standings = s.tournament.get_standings(&id);
+    assert_eq!(standings.get(0).unwrap().points, 0);
+    assert_eq!(standings.get(1).unwrap().points, 0);
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Finished);
+}
+
+// ============================================================================
+// Admin Tests
+// ============================================================================
+
+#[test]
+fn test_abort_refunds_a_running_tournament() {
+    let s = setup_test(2);
+    let id = s.run(Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournament.start_match(&id, &0);
+
+    s.tournament.abort(&id);
+
+    assert_eq!(s.env.auths()[0].0, s.admin);
+    assert_eq!(s.token.balance(&s.player(0)), STARTING_TOKENS);
+    assert_eq!(s.token.balance(&s.player(1)), STARTING_TOKENS);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+    assert_eq!(s.tournament.get_tournament(&id).phase, Phase::Cancelled);
+
+    assert_tournament_error(&s.tournament.try_abort(&id), Error::NotRunning);
+    assert_tournament_error(&s.tournament.try_report_result(&id, &0), Error::NotRunning);
+}
@@ -1,45 +1,17 @@
 #![cfg(test)]
 
-// Unit tests for the twenty-one contract using a simple mock GameHub.
+// Unit tests for the twenty-one contract against the shared RecordingGameHub
+// from game-hub-client.
 // These tests verify game logic independently of the full GameHub system.
 //
-// Note: These tests use a minimal mock for isolation and speed.
-// For full integration tests with the real GameHub contract, see:
-// contracts/game_hub/src/tests/twenty_one_integration.rs
+// Note: The recording hub keeps calls but does no session bookkeeping.
+// For integration tests with the real GameHub contract, see:
+// contracts/game-hub/src/test.rs
 
 use crate::{Error, TwentyOneContract, TwentyOneContractClient};
+use game_hub_client::testutils::{HubCall, RecordingGameHub, RecordingGameHubClient, StartGameCall};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
-
-// ============================================================================
-// Mock GameHub for Unit Testing
-// ============================================================================
-
-#[contract]
-pub struct MockGameHub;
-
-#[contractimpl]
-impl MockGameHub {
-    pub fn start_game(
-        _env: Env,
-        _game_id: Address,
-        _session_id: u32,
-        _player1: Address,
-        _player2: Address,
-        _player1_points: i128,
-        _player2_points: i128,
-    ) {
-        // Mock implementation - does nothing
-    }
-
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
-        // Mock implementation - does nothing
-    }
-
-    pub fn add_game(_env: Env, _game_address: Address) {
-        // Mock implementation - does nothing
-    }
-}
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env};
 
 // ============================================================================
 // Test Helpers
@@ -48,7 +20,7 @@ impl MockGameHub {
 fn setup_test() -> (
     Env,
     TwentyOneContractClient<'static>,
-    MockGameHubClient<'static>,
+    RecordingGameHubClient<'static>,
     Address,
     Address,
 ) {
@@ -67,9 +39,9 @@ fn setup_test() -> (
         max_entry_ttl: u32::MAX / 2,
     });
 
-    // Deploy mock GameHub contract
-    let hub_addr = env.register(MockGameHub, ());
-    let game_hub = MockGameHubClient::new(&env, &hub_addr);
+    // Deploy recording GameHub contract
+    let hub_addr = env.register(RecordingGameHub, ());
+    let game_hub = RecordingGameHubClient::new(&env, &hub_addr);
 
     // Create admin address
     let admin = Address::generate(&env);
@@ -78,9 +50,6 @@ fn setup_test() -> (
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
     let client = TwentyOneContractClient::new(&env, &contract_id);
 
-    // Register twenty-one as a whitelisted game (mock does nothing)
-    game_hub.add_game(&contract_id);
-
     let player1 = Address::generate(&env);
     let player2 = Address::generate(&env);
 
@@ -739,7 +708,7 @@ fn test_get_admin() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
 
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
     let client = TwentyOneContractClient::new(&env, &contract_id);
@@ -754,7 +723,7 @@ fn test_get_hub() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
 
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
     let client = TwentyOneContractClient::new(&env, &contract_id);
@@ -770,7 +739,7 @@ fn test_set_admin() {
 
     let admin = Address::generate(&env);
     let new_admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
 
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
     let client = TwentyOneContractClient::new(&env, &contract_id);
@@ -788,7 +757,7 @@ fn test_set_hub() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
     let new_hub_addr = Address::generate(&env);
 
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
@@ -807,7 +776,7 @@ fn test_upgrade_function_exists() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let hub_addr = env.register(MockGameHub, ());
+    let hub_addr = env.register(RecordingGameHub, ());
 
     let contract_id = env.register(TwentyOneContract, (&admin, &hub_addr));
     let client = TwentyOneContractClient::new(&env, &contract_id);
@@ -870,3 +839,114 @@ fn test_cannot_play_against_self() {
     let result = client.try_start_game(&session_id, &player1, &player1, &100_0000000, &100_0000000);
     assert_twenty_one_error(&result, Error::SelfPlay);
 }
+
+// ============================================================================
+// Game Hub Call Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_reports_session_to_hub() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    client.start_game(&40u32, &player1, &player2, &100_0000000, &50_0000000);
+
+    let start = StartGameCall {
+        game_id: client.address.clone(),
+        session_id: 40,
+        player1,
+        player2,
+        player1_points: 100_0000000,
+        player2_points: 50_0000000,
+    };
+    assert_eq!(hub.calls(), vec![&env, HubCall::StartGame(start)]);
+}
+
+#[test]
+fn test_reveal_reports_higher_hand_to_hub() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    // Find a session whose opening hands aren't a draw
+    let mut session_id = 41u32;
+    loop {
+        client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+        if client.get_hand_value(&session_id, &player1) != client.get_hand_value(&session_id, &player2) {
+            break;
+        }
+        session_id += 1;
+    }
+    let player1_value = client.get_hand_value(&session_id, &player1);
+    let player2_value = client.get_hand_value(&session_id, &player2);
+
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    let winner = client.reveal_winner(&session_id);
+    assert_eq!(winner == player1, player1_value > player2_value);
+
+    let calls = hub.calls_for(&session_id);
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls.get(1), Some(HubCall::EndGame(session_id, player1_value > player2_value)));
+
+    // Revealing again returns the stored winner without ending the session twice
+    client.reveal_winner(&session_id);
+    assert_eq!(hub.calls_for(&session_id).len(), 2);
+}
+
+#[test]
+fn test_bust_reports_opponent_win_to_hub() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 60u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    for _ in 0..20 {
+        client.hit(&session_id, &player2);
+        if client.get_game(&session_id).winner.is_some() {
+            break;
+        }
+        // Hits that don't bust leave the hub alone
+        assert_eq!(hub.calls_for(&session_id).len(), 1);
+    }
+
+    assert_eq!(client.get_game(&session_id).winner, Some(player1.clone()));
+    assert_eq!(hub.calls_for(&session_id).get(1), Some(HubCall::EndGame(session_id, true)));
+}
+
+#[test]
+fn test_failed_hub_start_stores_no_game() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    hub.set_failing(&true);
+    let result = client.try_start_game(&61u32, &player1, &player2, &100_0000000, &100_0000000);
+    assert!(result.is_err());
+
+    let result = client.try_get_game(&61u32);
+    assert_twenty_one_error(&result, Error::GameNotFound);
+    assert_eq!(hub.call_count(), 0);
+}
+
+#[test]
+fn test_failed_hub_end_rolls_back_bust() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 62u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // Hit with the hub down until the bust is rejected
+    hub.set_failing(&true);
+    let mut cards = client.get_game(&session_id).player1_hand.len();
+    while client.try_hit(&session_id, &player1).is_ok() {
+        cards += 1;
+    }
+
+    // The busting card and the win were both dropped
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_hand.len(), cards);
+    assert!(game.winner.is_none());
+    assert_eq!(hub.player1_won(&session_id), None);
+
+    // Once the hub accepts the call, the same card busts player 1
+    hub.set_failing(&false);
+    client.hit(&session_id, &player1);
+    assert_eq!(client.get_game(&session_id).winner, Some(player2));
+    assert_eq!(hub.player1_won(&session_id), Some(false));
+}
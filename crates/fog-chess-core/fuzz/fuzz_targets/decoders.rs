@@ -0,0 +1,39 @@
+//! Feeds arbitrary bytes to every decoder the guests and host run on untrusted
+//! input. Run with `cargo +nightly fuzz run decoders` from crates/fog-chess-core.
+
+#![no_main]
+
+use fog_chess_core::journal::{decode_batch_journal, decode_move_journal, encode_move_journal, ReplayJournal};
+use fog_chess_core::{is_legal_move, Board, Color, Square};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((sq, color)) = decode_move_journal(data) {
+        assert!(sq.row() < 8 && sq.col() < 8);
+        assert_eq!(&encode_move_journal(sq, color)[..], data);
+    }
+
+    if let Ok(batch) = decode_batch_journal(data) {
+        assert_eq!(batch.end_positions().count(), batch.len());
+    }
+
+    if let Ok(journal) = ReplayJournal::decode(data) {
+        assert_eq!(&journal.encode()[..], data);
+    }
+
+    // Two sides, then move pairs to replay over them
+    if data.len() >= 128 {
+        if let Ok(mut board) = Board::decode(&data[..64], &data[64..128]) {
+            let mut color = Color::White;
+            for mv in data[128..].chunks(2) {
+                let (Some(from), Some(to)) = (Square::from_index(mv[0] % 64), Square::from_index(mv.get(1).copied().unwrap_or(0) % 64)) else {
+                    continue;
+                };
+                if is_legal_move(&board, color, from, to) {
+                    board.apply(from, to);
+                    color = color.opposite();
+                }
+            }
+        }
+    }
+});
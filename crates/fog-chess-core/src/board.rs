@@ -0,0 +1,91 @@
+use crate::journal::DecodeError;
+use crate::piece::{Color, Piece, Square};
+
+pub const BOARD_SQUARES: usize = 64;
+
+/// Both sides' pieces. Each side is the 64-byte layout the replay circuit and
+/// `reveal_board` use: one byte per square (`row * 8 + col`), 0 = empty,
+/// otherwise a piece code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Board {
+    sides: [[u8; BOARD_SQUARES]; 2],
+}
+
+impl Board {
+    pub const fn empty() -> Board {
+        Board { sides: [[0; BOARD_SQUARES]; 2] }
+    }
+
+    /// The frontend's starting layout: black on rows 0–1, white on rows 6–7.
+    pub fn initial() -> Board {
+        const BACK_RANK: [Piece; 8] = [
+            Piece::Rook, Piece::Knight, Piece::Bishop, Piece::Queen,
+            Piece::King, Piece::Bishop, Piece::Knight, Piece::Rook,
+        ];
+        let mut board = Board::empty();
+        for (col, piece) in (0..8).zip(BACK_RANK) {
+            for (color, back_row) in [(Color::Black, 0), (Color::White, 7)] {
+                board.set(Square::new(back_row, col).unwrap(), Some((color, piece)));
+                board.set(Square::new(color.pawn_start_row(), col).unwrap(), Some((color, Piece::Pawn)));
+            }
+        }
+        board
+    }
+
+    /// Decode two revealed sides. Each needs 64 squares of valid codes and
+    /// exactly one king, and the sides may not share a square.
+    pub fn decode(white: &[u8], black: &[u8]) -> Result<Board, DecodeError> {
+        let mut board = Board::empty();
+        for (color, bytes) in [(Color::White, white), (Color::Black, black)] {
+            board.sides[color as usize] = decode_side(bytes)?;
+        }
+        if (0..BOARD_SQUARES).any(|sq| board.sides[0][sq] != 0 && board.sides[1][sq] != 0) {
+            return Err(DecodeError::Overlap);
+        }
+        Ok(board)
+    }
+
+    pub fn side(&self, color: Color) -> &[u8; BOARD_SQUARES] {
+        &self.sides[color as usize]
+    }
+
+    pub fn piece_at(&self, sq: Square) -> Option<(Color, Piece)> {
+        [Color::White, Color::Black]
+            .into_iter()
+            .find_map(|color| Piece::from_code(self.sides[color as usize][sq.index()]).map(|piece| (color, piece)))
+    }
+
+    pub fn is_occupied(&self, sq: Square) -> bool {
+        self.sides[0][sq.index()] != 0 || self.sides[1][sq.index()] != 0
+    }
+
+    /// Put `piece` on `sq`, replacing whatever was there.
+    pub fn set(&mut self, sq: Square, piece: Option<(Color, Piece)>) {
+        self.sides[0][sq.index()] = 0;
+        self.sides[1][sq.index()] = 0;
+        if let Some((color, piece)) = piece {
+            self.sides[color as usize][sq.index()] = piece.code();
+        }
+    }
+
+    /// Move whatever stands on `from` to `to` without checking legality.
+    /// Returns the captured piece, if any.
+    pub fn apply(&mut self, from: Square, to: Square) -> Option<(Color, Piece)> {
+        let moving = self.piece_at(from);
+        let captured = self.piece_at(to);
+        self.set(from, None);
+        self.set(to, moving);
+        captured
+    }
+}
+
+fn decode_side(bytes: &[u8]) -> Result<[u8; BOARD_SQUARES], DecodeError> {
+    let side: [u8; BOARD_SQUARES] = bytes.try_into().map_err(|_| DecodeError::Length)?;
+    if side.iter().any(|&code| code != 0 && Piece::from_code(code).is_none()) {
+        return Err(DecodeError::UnknownPiece);
+    }
+    if side.iter().filter(|&&code| code == Piece::King.code()).count() != 1 {
+        return Err(DecodeError::KingCount);
+    }
+    Ok(side)
+}
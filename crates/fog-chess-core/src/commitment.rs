@@ -0,0 +1,46 @@
+//! Commitment hashes. The contract recomputes `board_commitment` with the
+//! Soroban host's sha256; both must stay byte for byte the same.
+
+use sha2::{Digest, Sha256};
+
+use crate::journal::encode_move_journal;
+use crate::piece::{Color, Square};
+
+/// What the move circuit checks a piece against:
+/// SHA256(row ‖ col ‖ piece code ‖ color code ‖ salt as big-endian u32).
+/// The piece and color are in the preimage so a prover can't claim another
+/// piece type or side for the same commitment.
+pub fn square_commitment(pos: [u8; 2], piece: u8, color: u8, salt: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(pos);
+    hasher.update([piece, color]);
+    hasher.update(salt.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// What `commit_board` stores for a whole side: SHA256(64-byte board ‖ salt as big-endian u32).
+/// Takes the raw bytes so a malformed board still hashes as the player sent it.
+pub fn board_commitment(side: &[u8], salt: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(side);
+    hasher.update(salt.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// One step of a side's move chain: SHA256(chain ‖ SHA256(move journal)).
+/// The contract extends the chain with every move it verifies, starting from
+/// 32 zero bytes, and the replay circuit rebuilds it from the replayed moves.
+pub fn extend_move_chain(chain: &[u8; 32], journal_sha256: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(chain);
+    hasher.update(journal_sha256);
+    hasher.finalize().into()
+}
+
+/// The move chain over `color`'s end squares in play order.
+pub fn move_chain(ends: impl IntoIterator<Item = Square>, color: Color) -> [u8; 32] {
+    ends.into_iter().fold([0; 32], |chain, end| {
+        let journal_sha256: [u8; 32] = Sha256::digest(encode_move_journal(end, color)).into();
+        extend_move_chain(&chain, &journal_sha256)
+    })
+}
@@ -0,0 +1,153 @@
+//! Encoders and decoders for what the guests commit. The contract parses the
+//! same layouts byte by byte.
+
+use core::fmt;
+
+use crate::piece::{Color, Square};
+
+/// Why a board or journal failed to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    Length,
+    OffBoard,
+    UnknownPiece,
+    KingCount,
+    Overlap,
+    UnknownResult,
+    UnknownColor,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DecodeError::Length => "wrong length",
+            DecodeError::OffBoard => "square off the board",
+            DecodeError::UnknownPiece => "unknown piece code",
+            DecodeError::KingCount => "each side needs exactly one king",
+            DecodeError::Overlap => "both sides on one square",
+            DecodeError::UnknownResult => "unknown game result",
+            DecodeError::UnknownColor => "unknown color code",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// Move journal: `(end_pos, color)` through risc0 serde, so each byte is its
+/// own little-endian u32 word — 12 bytes in all.
+pub const MOVE_JOURNAL_LEN: usize = 12;
+
+pub fn encode_move_journal(end: Square, color: Color) -> [u8; MOVE_JOURNAL_LEN] {
+    let [row, col] = end.pos();
+    [row, 0, 0, 0, col, 0, 0, 0, color.code(), 0, 0, 0]
+}
+
+pub fn decode_move_journal(bytes: &[u8]) -> Result<(Square, Color), DecodeError> {
+    let bytes: &[u8; MOVE_JOURNAL_LEN] = bytes.try_into().map_err(|_| DecodeError::Length)?;
+    let word = |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    let (row, col, color) = (word(0), word(4), word(8));
+    if row >= 8 || col >= 8 {
+        return Err(DecodeError::OffBoard);
+    }
+    let square = Square::new(row as u8, col as u8).ok_or(DecodeError::OffBoard)?;
+    let color = u8::try_from(color).ok().and_then(Color::from_code).ok_or(DecodeError::UnknownColor)?;
+    Ok((square, color))
+}
+
+/// Batch journal: move image id (32) ‖ color (1) ‖ end_pos (2) per move.
+/// Every move in a batch is played by the one side named by `color`.
+pub struct BatchJournal<'a> {
+    pub move_image_id: [u8; 32],
+    pub color:         Color,
+    end_positions:     &'a [u8],
+}
+
+impl BatchJournal<'_> {
+    pub fn len(&self) -> usize {
+        self.end_positions.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end_positions.is_empty()
+    }
+
+    pub fn end_positions(&self) -> impl Iterator<Item = Square> + '_ {
+        // Checked on decode
+        (0..self.len()).filter_map(|i| Square::new(self.end_positions[2 * i], self.end_positions[2 * i + 1]))
+    }
+}
+
+pub fn decode_batch_journal(bytes: &[u8]) -> Result<BatchJournal<'_>, DecodeError> {
+    if bytes.len() < 33 + 2 || !(bytes.len() - 33).is_multiple_of(2) {
+        return Err(DecodeError::Length);
+    }
+    let (image_id, rest) = bytes.split_at(32);
+    let color = Color::from_code(rest[0]).ok_or(DecodeError::UnknownColor)?;
+    let end_positions = &rest[1..];
+    if end_positions.iter().any(|&b| b >= 8) {
+        return Err(DecodeError::OffBoard);
+    }
+    Ok(BatchJournal { move_image_id: image_id.try_into().map_err(|_| DecodeError::Length)?, color, end_positions })
+}
+
+/// Outcome the replay circuit commits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GameResult {
+    Undecided = 0,
+    WhiteWins = 1,
+    BlackWins = 2,
+}
+
+/// Replay journal: white commitment (32) ‖ black commitment (32) ‖ white
+/// move chain (32) ‖ black move chain (32) ‖ plies (u32 BE) ‖ result (1).
+/// The move chains (see `commitment::move_chain`) tie the replayed game to
+/// the moves the contract verified while it was played.
+pub const REPLAY_JOURNAL_LEN: usize = 32 * 4 + 4 + 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayJournal {
+    pub white_commitment: [u8; 32],
+    pub black_commitment: [u8; 32],
+    pub white_moves:      [u8; 32],
+    pub black_moves:      [u8; 32],
+    pub plies:            u32,
+    pub result:           GameResult,
+}
+
+impl ReplayJournal {
+    pub fn encode(&self) -> [u8; REPLAY_JOURNAL_LEN] {
+        let mut out = [0u8; REPLAY_JOURNAL_LEN];
+        out[..32].copy_from_slice(&self.white_commitment);
+        out[32..64].copy_from_slice(&self.black_commitment);
+        out[64..96].copy_from_slice(&self.white_moves);
+        out[96..128].copy_from_slice(&self.black_moves);
+        out[128..132].copy_from_slice(&self.plies.to_be_bytes());
+        out[132] = self.result as u8;
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<ReplayJournal, DecodeError> {
+        let bytes: &[u8; REPLAY_JOURNAL_LEN] = bytes.try_into().map_err(|_| DecodeError::Length)?;
+        let result = match bytes[132] {
+            0 => GameResult::Undecided,
+            1 => GameResult::WhiteWins,
+            2 => GameResult::BlackWins,
+            _ => return Err(DecodeError::UnknownResult),
+        };
+        let hash = |at: usize| {
+            let mut out = [0u8; 32];
+            out.copy_from_slice(&bytes[at..at + 32]);
+            out
+        };
+        Ok(ReplayJournal {
+            white_commitment: hash(0),
+            black_commitment: hash(32),
+            white_moves:      hash(64),
+            black_moves:      hash(96),
+            plies:            u32::from_be_bytes([bytes[128], bytes[129], bytes[130], bytes[131]]),
+            result,
+        })
+    }
+}
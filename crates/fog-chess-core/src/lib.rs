@@ -0,0 +1,24 @@
+//! Lantern Chess rules, shared by the zkVM guests, the prover host and the
+//! contracts' tests.
+//!
+//! Squares are `[row, col]` as the frontend draws them: row 0 is black's back
+//! rank, row 7 white's. Pieces use the circuit's `piece_type` codes 1–6.
+//! Fog-of-war rules: no castling, en passant or promotion, and check is only
+//! a warning — a game ends when a king is captured.
+//!
+//! `no_std` by default for the guests and Soroban; the `std` feature adds the
+//! error trait impls the host wants.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod board;
+pub mod commitment;
+pub mod journal;
+pub mod piece;
+pub mod rules;
+
+pub use board::{Board, BOARD_SQUARES};
+pub use commitment::{board_commitment, extend_move_chain, move_chain, square_commitment};
+pub use journal::DecodeError;
+pub use piece::{Color, Piece, Square};
+pub use rules::{is_legal_move, is_legal_shape, legal_destinations, legal_moves};
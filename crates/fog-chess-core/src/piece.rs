@@ -0,0 +1,133 @@
+/// Piece codes as the circuits and the frontend's `PIECE_TYPE_MAP` use them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Piece {
+    Knight = 1,
+    Rook   = 2,
+    Bishop = 3,
+    Pawn   = 4,
+    Queen  = 5,
+    King   = 6,
+}
+
+impl Piece {
+    pub const ALL: [Piece; 6] = [Piece::Knight, Piece::Rook, Piece::Bishop, Piece::Pawn, Piece::Queen, Piece::King];
+
+    pub const fn from_code(code: u8) -> Option<Piece> {
+        match code {
+            1 => Some(Piece::Knight),
+            2 => Some(Piece::Rook),
+            3 => Some(Piece::Bishop),
+            4 => Some(Piece::Pawn),
+            5 => Some(Piece::Queen),
+            6 => Some(Piece::King),
+            _ => None,
+        }
+    }
+
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+/// White is player1 and moves first, up the board towards row 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Color {
+    White = 0,
+    Black = 1,
+}
+
+impl Color {
+    pub const fn from_code(code: u8) -> Option<Color> {
+        match code {
+            0 => Some(Color::White),
+            1 => Some(Color::Black),
+            _ => None,
+        }
+    }
+
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    /// Row delta of a single pawn step
+    pub const fn pawn_direction(self) -> i8 {
+        match self {
+            Color::White => -1,
+            Color::Black => 1,
+        }
+    }
+
+    /// Row pawns start on, and may double-step from
+    pub const fn pawn_start_row(self) -> u8 {
+        match self {
+            Color::White => 6,
+            Color::Black => 1,
+        }
+    }
+}
+
+/// A square on the board, `row * 8 + col`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square(u8);
+
+impl Square {
+    pub const fn new(row: u8, col: u8) -> Option<Square> {
+        if row < 8 && col < 8 {
+            Some(Square(row * 8 + col))
+        } else {
+            None
+        }
+    }
+
+    pub const fn from_pos(pos: [u8; 2]) -> Option<Square> {
+        Square::new(pos[0], pos[1])
+    }
+
+    pub const fn from_index(index: u8) -> Option<Square> {
+        if index < 64 {
+            Some(Square(index))
+        } else {
+            None
+        }
+    }
+
+    pub const fn row(self) -> u8 {
+        self.0 / 8
+    }
+
+    pub const fn col(self) -> u8 {
+        self.0 % 8
+    }
+
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub const fn pos(self) -> [u8; 2] {
+        [self.row(), self.col()]
+    }
+
+    /// The square `dr` rows and `dc` columns away, if it is on the board
+    pub const fn offset(self, dr: i8, dc: i8) -> Option<Square> {
+        let row = self.row() as i8 + dr;
+        let col = self.col() as i8 + dc;
+        if row < 0 || row > 7 || col < 0 || col > 7 {
+            None
+        } else {
+            Some(Square((row * 8 + col) as u8))
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = Square> {
+        (0..64).map(Square)
+    }
+}
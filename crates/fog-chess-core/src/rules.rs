@@ -0,0 +1,86 @@
+use crate::board::Board;
+use crate::piece::{Color, Piece, Square};
+
+/// Geometry only — all the single-move circuit can check, since it never sees
+/// the rest of the board. Sliders may be blocked and a pawn's diagonal step
+/// may have nothing to capture; `is_legal_move` settles that.
+pub fn is_legal_shape(piece: Piece, color: Color, from: Square, to: Square) -> bool {
+    let dr = to.row() as i8 - from.row() as i8;
+    let dc = to.col() as i8 - from.col() as i8;
+    let (row_diff, col_diff) = (dr.abs(), dc.abs());
+    if row_diff + col_diff == 0 {
+        return false;
+    }
+
+    match piece {
+        Piece::Knight => (row_diff == 2 && col_diff == 1) || (row_diff == 1 && col_diff == 2),
+        Piece::Rook => dr == 0 || dc == 0,
+        Piece::Bishop => row_diff == col_diff,
+        Piece::Queen => dr == 0 || dc == 0 || row_diff == col_diff,
+        Piece::King => row_diff <= 1 && col_diff <= 1,
+        Piece::Pawn => {
+            let dir = color.pawn_direction();
+            (dc == 0 && dr == dir)
+                || (dc == 0 && dr == 2 * dir && from.row() == color.pawn_start_row())
+                || (col_diff == 1 && dr == dir)
+        }
+    }
+}
+
+/// Every square strictly between `from` and `to` is empty. Only meaningful
+/// for squares on one line.
+fn path_clear(board: &Board, from: Square, to: Square) -> bool {
+    let dr = (to.row() as i8 - from.row() as i8).signum();
+    let dc = (to.col() as i8 - from.col() as i8).signum();
+    let mut sq = from;
+    loop {
+        sq = match sq.offset(dr, dc) {
+            Some(next) if next != to => next,
+            _ => return true,
+        };
+        if board.is_occupied(sq) {
+            return false;
+        }
+    }
+}
+
+/// Whether the piece of `color` on `from` may move to `to` — the same rules
+/// as the frontend's chessLogic.ts.
+pub fn is_legal_move(board: &Board, color: Color, from: Square, to: Square) -> bool {
+    let piece = match board.piece_at(from) {
+        Some((owner, piece)) if owner == color => piece,
+        _ => return false,
+    };
+    let target = board.piece_at(to);
+    if matches!(target, Some((owner, _)) if owner == color) {
+        return false; // can't capture your own piece
+    }
+    if !is_legal_shape(piece, color, from, to) {
+        return false;
+    }
+
+    match piece {
+        Piece::Knight | Piece::King => true,
+        Piece::Rook | Piece::Bishop | Piece::Queen => path_clear(board, from, to),
+        Piece::Pawn => {
+            if from.col() == to.col() {
+                target.is_none() && path_clear(board, from, to)
+            } else {
+                target.is_some() // diagonal steps only capture
+            }
+        }
+    }
+}
+
+/// Squares the piece on `from` may move to, for whichever side owns it.
+pub fn legal_destinations(board: &Board, from: Square) -> impl Iterator<Item = Square> + '_ {
+    let owner = board.piece_at(from).map(|(color, _)| color);
+    Square::all().filter(move |&to| owner.is_some_and(|color| is_legal_move(board, color, from, to)))
+}
+
+/// Every legal (from, to) for `color`, in square order.
+pub fn legal_moves(board: &Board, color: Color) -> impl Iterator<Item = (Square, Square)> + '_ {
+    Square::all()
+        .filter(move |&from| matches!(board.piece_at(from), Some((owner, _)) if owner == color))
+        .flat_map(move |from| legal_destinations(board, from).map(move |to| (from, to)))
+}
@@ -0,0 +1,278 @@
+//! Property tests: the fog rules against shakmaty's move generator on random
+//! positions, and round trips through the journal encodings.
+//!
+//! Our row 0 is black's back rank, so row `r` is rank `8 - r` and column `c`
+//! is file `a + c`; white pawns advance towards row 0, up the ranks.
+
+use fog_chess_core::journal::{
+    decode_batch_journal, decode_move_journal, encode_move_journal, GameResult, ReplayJournal,
+};
+use fog_chess_core::{
+    board_commitment, extend_move_chain, is_legal_move, is_legal_shape, legal_moves, move_chain, square_commitment, Board, Color, DecodeError,
+    Piece, Square,
+};
+use proptest::prelude::*;
+use shakmaty::{attacks, Bitboard, File, Rank};
+
+fn to_shakmaty(sq: Square) -> shakmaty::Square {
+    shakmaty::Square::from_coords(File::new(sq.col() as u32), Rank::new(7 - sq.row() as u32))
+}
+
+fn shakmaty_color(color: Color) -> shakmaty::Color {
+    match color {
+        Color::White => shakmaty::Color::White,
+        Color::Black => shakmaty::Color::Black,
+    }
+}
+
+fn occupied_by(board: &Board, color: Option<Color>) -> Bitboard {
+    Square::all()
+        .filter(|&sq| match board.piece_at(sq) {
+            Some((owner, _)) => color.is_none_or(|c| c == owner),
+            None => false,
+        })
+        .map(to_shakmaty)
+        .collect()
+}
+
+/// Destinations shakmaty allows, minus castling, en passant and promotion
+/// choice — none of which Lantern Chess has.
+fn reference_moves(board: &Board, color: Color, from: Square) -> Bitboard {
+    let Some((owner, piece)) = board.piece_at(from) else { return Bitboard::EMPTY };
+    if owner != color {
+        return Bitboard::EMPTY;
+    }
+    let occupied = occupied_by(board, None);
+    let own = occupied_by(board, Some(color));
+    let enemy = occupied_by(board, Some(color.opposite()));
+    let sq = to_shakmaty(from);
+
+    match piece {
+        Piece::Knight => attacks::knight_attacks(sq) & !own,
+        Piece::Rook => attacks::rook_attacks(sq, occupied) & !own,
+        Piece::Bishop => attacks::bishop_attacks(sq, occupied) & !own,
+        Piece::Queen => attacks::queen_attacks(sq, occupied) & !own,
+        Piece::King => attacks::king_attacks(sq) & !own,
+        Piece::Pawn => {
+            let mut moves = attacks::pawn_attacks(shakmaty_color(color), sq) & enemy;
+            let dir = color.pawn_direction();
+            if let Some(one) = from.offset(dir, 0).filter(|&s| !board.is_occupied(s)) {
+                moves.add(to_shakmaty(one));
+                if from.row() == color.pawn_start_row() {
+                    if let Some(two) = one.offset(dir, 0).filter(|&s| !board.is_occupied(s)) {
+                        moves.add(to_shakmaty(two));
+                    }
+                }
+            }
+            moves
+        }
+    }
+}
+
+fn piece() -> impl Strategy<Value = Piece> {
+    prop::sample::select(&Piece::ALL[..5]) // kings are placed separately
+}
+
+/// A position with one king per side and up to 24 other pieces anywhere.
+fn position() -> impl Strategy<Value = Board> {
+    (
+        prop::collection::vec((0u8..64, any::<bool>(), piece()), 0..24),
+        0u8..64,
+        0u8..64,
+    )
+        .prop_filter("kings share a square", |(_, white_king, black_king)| white_king != black_king)
+        .prop_map(|(pieces, white_king, black_king)| {
+            let mut board = Board::empty();
+            for (index, black, piece) in pieces {
+                let color = if black { Color::Black } else { Color::White };
+                board.set(Square::from_index(index).unwrap(), Some((color, piece)));
+            }
+            board.set(Square::from_index(white_king).unwrap(), Some((Color::White, Piece::King)));
+            board.set(Square::from_index(black_king).unwrap(), Some((Color::Black, Piece::King)));
+            board
+        })
+}
+
+fn color() -> impl Strategy<Value = Color> {
+    prop_oneof![Just(Color::White), Just(Color::Black)]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn legal_moves_match_reference(board in position(), color in color()) {
+        for from in Square::all() {
+            let expected = reference_moves(&board, color, from);
+            for to in Square::all() {
+                prop_assert_eq!(
+                    is_legal_move(&board, color, from, to),
+                    expected.contains(to_shakmaty(to)),
+                    "{:?} {:?} -> {:?} on {:?}", color, from.pos(), to.pos(), board,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn move_generation_matches_reference(board in position(), color in color()) {
+        let expected: u32 = Square::all().map(|from| reference_moves(&board, color, from).count() as u32).sum();
+        prop_assert_eq!(legal_moves(&board, color).count() as u32, expected);
+    }
+
+    #[test]
+    fn legal_moves_have_legal_shape(board in position(), color in color()) {
+        for from in Square::all() {
+            let Some((owner, piece)) = board.piece_at(from) else { continue };
+            for to in Square::all() {
+                if owner == color && is_legal_move(&board, color, from, to) {
+                    prop_assert!(is_legal_shape(piece, color, from, to));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn board_decodes_its_own_sides(board in position()) {
+        let decoded = Board::decode(board.side(Color::White), board.side(Color::Black));
+        prop_assert_eq!(decoded, Ok(board));
+    }
+
+    #[test]
+    fn move_journal_round_trips(index in 0u8..64, black in any::<bool>()) {
+        let sq = Square::from_index(index).unwrap();
+        let color = if black { Color::Black } else { Color::White };
+        prop_assert_eq!(decode_move_journal(&encode_move_journal(sq, color)), Ok((sq, color)));
+    }
+
+    #[test]
+    fn replay_journal_round_trips(
+        white in any::<[u8; 32]>(),
+        black in any::<[u8; 32]>(),
+        white_moves in any::<[u8; 32]>(),
+        black_moves in any::<[u8; 32]>(),
+        plies in any::<u32>(),
+        result in prop_oneof![Just(GameResult::Undecided), Just(GameResult::WhiteWins), Just(GameResult::BlackWins)],
+    ) {
+        let journal = ReplayJournal {
+            white_commitment: white,
+            black_commitment: black,
+            white_moves,
+            black_moves,
+            plies,
+            result,
+        };
+        prop_assert_eq!(ReplayJournal::decode(&journal.encode()), Ok(journal));
+    }
+
+    #[test]
+    fn decoders_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..200)) {
+        let _ = decode_move_journal(&bytes);
+        let _ = decode_batch_journal(&bytes);
+        let _ = ReplayJournal::decode(&bytes);
+        if bytes.len() >= 128 {
+            let _ = Board::decode(&bytes[..64], &bytes[64..128]);
+        }
+    }
+}
+
+#[test]
+fn initial_position_has_twenty_moves_a_side() {
+    let board = Board::initial();
+    assert_eq!(Board::decode(board.side(Color::White), board.side(Color::Black)), Ok(board.clone()));
+    assert_eq!(legal_moves(&board, Color::White).count(), 20);
+    assert_eq!(legal_moves(&board, Color::Black).count(), 20);
+    assert_eq!(board.piece_at(Square::new(7, 4).unwrap()), Some((Color::White, Piece::King)));
+    assert_eq!(board.piece_at(Square::new(0, 3).unwrap()), Some((Color::Black, Piece::Queen)));
+}
+
+#[test]
+fn board_commitment_covers_every_square_and_the_salt() {
+    let mut board = Board::initial();
+    let before = board_commitment(board.side(Color::White), 1);
+    assert_ne!(before, board_commitment(board.side(Color::White), 2));
+
+    board.apply(Square::new(6, 4).unwrap(), Square::new(4, 4).unwrap());
+    assert_ne!(before, board_commitment(board.side(Color::White), 1));
+}
+
+#[test]
+fn square_commitment_binds_piece_and_color() {
+    let knight = square_commitment([7, 1], Piece::Knight.code(), Color::White.code(), 1);
+    assert_ne!(knight, square_commitment([7, 1], Piece::Queen.code(), Color::White.code(), 1));
+    assert_ne!(knight, square_commitment([7, 1], Piece::Knight.code(), Color::Black.code(), 1));
+    assert_ne!(knight, square_commitment([7, 2], Piece::Knight.code(), Color::White.code(), 1));
+}
+
+#[test]
+fn move_chain_follows_order_and_color() {
+    let (e4, d5) = (Square::new(4, 4).unwrap(), Square::new(3, 3).unwrap());
+    assert_eq!(move_chain([], Color::White), [0; 32]);
+
+    let chain = move_chain([e4, d5], Color::White);
+    let after_e4 = move_chain([e4], Color::White);
+    assert_ne!(after_e4, [0; 32]);
+    assert_eq!(chain, extend_move_chain(&after_e4, &extend_input(d5, Color::White)));
+    assert_ne!(chain, move_chain([d5, e4], Color::White));
+    assert_ne!(chain, move_chain([e4, d5], Color::Black));
+}
+
+/// SHA256 of a move journal, the way the contract sees a verified move
+fn extend_input(end: Square, color: Color) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(encode_move_journal(end, color)).into()
+}
+
+#[test]
+fn journals_reject_unknown_colors() {
+    let mut journal = encode_move_journal(Square::new(3, 4).unwrap(), Color::Black);
+    journal[8] = 2;
+    assert_eq!(decode_move_journal(&journal), Err(DecodeError::UnknownColor));
+
+    let mut batch = [0u8; 32 + 1 + 4];
+    batch[33..].copy_from_slice(&[4, 4, 3, 4]);
+    let decoded = decode_batch_journal(&batch).unwrap();
+    assert_eq!(decoded.color, Color::White);
+    assert_eq!(decoded.len(), 2);
+    batch[32] = 2;
+    assert!(matches!(decode_batch_journal(&batch), Err(DecodeError::UnknownColor)));
+}
+
+#[test]
+fn decode_rejects_malformed_boards() {
+    let mut white = [0u8; 64];
+    let mut black = [0u8; 64];
+    white[60] = Piece::King.code();
+    black[4] = Piece::King.code();
+    assert!(Board::decode(&white, &black).is_ok());
+
+    assert_eq!(Board::decode(&white[..63], &black), Err(DecodeError::Length));
+    assert_eq!(Board::decode(&white, &[0; 64]), Err(DecodeError::KingCount));
+
+    let mut bad = white;
+    bad[0] = 7;
+    assert_eq!(Board::decode(&bad, &black), Err(DecodeError::UnknownPiece));
+
+    let mut overlap = white;
+    overlap[4] = Piece::Pawn.code();
+    assert_eq!(Board::decode(&overlap, &black), Err(DecodeError::Overlap));
+}
+
+#[test]
+fn pawns_only_move_forward() {
+    let from = Square::new(4, 4).unwrap();
+    let up = Square::new(3, 4).unwrap();
+    let down = Square::new(5, 4).unwrap();
+    assert!(is_legal_shape(Piece::Pawn, Color::White, from, up));
+    assert!(!is_legal_shape(Piece::Pawn, Color::White, from, down));
+    assert!(is_legal_shape(Piece::Pawn, Color::Black, from, down));
+    assert!(!is_legal_shape(Piece::Pawn, Color::Black, from, up));
+}
+
+#[test]
+fn nothing_moves_in_place() {
+    let sq = Square::new(3, 3).unwrap();
+    for piece in Piece::ALL {
+        assert!(!is_legal_shape(piece, Color::White, sq, sq), "{piece:?}");
+    }
+}
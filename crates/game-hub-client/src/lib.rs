@@ -0,0 +1,126 @@
+#![no_std]
+
+//! # Game Hub client
+//!
+//! The one definition of the Game Hub interface that games call. `game-hub`
+//! and `mock-game-hub` both implement it, so a game built against
+//! `GameHubClient` can be pointed at either with `set_hub`. `GameHubReader` is
+//! the read side only `game-hub` has: registered games and session records,
+//! for contracts such as `tournament` that build on finished sessions.
+//!
+//! `Outcome`, `Session` and the event structs are the ones the hubs use and
+//! publish, so games, hubs, tests and indexers all share one definition. With
+//! the `testutils` feature, `testutils::RecordingGameHub` is a hub for unit
+//! tests that keeps every call and can be told to fail.
+
+use soroban_sdk::{contractclient, contractevent, contracttype, Address, Env};
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+/// How a game ended, as reported by the game contract.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Player1Won,
+    Player2Won,
+    Draw,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionStatus {
+    Active,
+    Ended,     // decided or drawn
+    Cancelled, // aborted; stakes refunded
+    Expired,   // ran past its deadline; settled by the game's expiry rule
+}
+
+/// A session as the hub keeps it, from `start_game` until long after it ends.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Session {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128, // escrowed while the session is active
+    pub player2_points: i128,
+    pub start_ledger: u32,
+    pub end_ledger: Option<u32>,
+    pub deadline_ledger: u32,        // expire_session is allowed after this ledger
+    pub waiting_on: Option<Address>, // player the game is waiting for, if it said
+    pub status: SessionStatus,
+    pub winner: Option<Address>, // set when the session ends with a winner; `Ended` without one is a draw
+}
+
+// Calls a game makes into the hub. The hub checks `game_id.require_auth()`,
+// which the calling game contract satisfies as the direct invoker.
+#[contractclient(name = "GameHubClient")]
+pub trait GameHub {
+    fn start_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    );
+
+    fn end_game(env: Env, session_id: u32, player1_won: bool);
+
+    fn end_game_with_outcome(env: Env, session_id: u32, outcome: Outcome);
+
+    fn extend_deadline(env: Env, session_id: u32, deadline_ledger: u32, waiting_on: Option<Address>);
+}
+
+// Reads from `game-hub`; `mock-game-hub` keeps no records to read.
+// `get_session` fails for an unknown id.
+#[contractclient(name = "GameHubReaderClient")]
+pub trait GameHubReader {
+    fn is_game_active(env: Env, game_id: Address) -> bool;
+
+    fn get_session(env: Env, session_id: u32) -> Session;
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameStarted {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameEnded {
+    pub session_id: u32,
+    pub player1_won: bool,
+}
+
+/// Published for draws and cancellations; wins publish `GameEnded`.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameSettled {
+    pub session_id: u32,
+    pub outcome: Outcome,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadlineExtended {
+    pub session_id: u32,
+    pub deadline_ledger: u32,
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,97 @@
+#![cfg(test)]
+
+// The client against the recording double, called from a contract the way a
+// game calls the hub.
+
+use crate::testutils::{HubCall, RecordingGameHub, RecordingGameHubClient, StartGameCall};
+use crate::{GameHubClient, Outcome};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{contract, contractimpl, vec, Address, Env};
+
+#[contract]
+pub struct Caller;
+
+#[contractimpl]
+impl Caller {
+    pub fn play(env: Env, hub: Address, session_id: u32, player1: Address, player2: Address) {
+        let hub = GameHubClient::new(&env, &hub);
+        hub.start_game(&env.current_contract_address(), &session_id, &player1, &player2, &10, &20);
+        hub.extend_deadline(&session_id, &500, &Some(player2));
+        hub.end_game_with_outcome(&session_id, &Outcome::Draw);
+        hub.end_game(&session_id, &true);
+    }
+}
+
+fn setup_test() -> (Env, RecordingGameHubClient<'static>, CallerClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let hub = RecordingGameHubClient::new(&env, &env.register(RecordingGameHub, ()));
+    let caller = CallerClient::new(&env, &env.register(Caller, ()));
+    (env, hub, caller)
+}
+
+#[test]
+fn test_records_every_call_in_order() {
+    let (env, hub, caller) = setup_test();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    caller.play(&hub.address, &7, &player1, &player2);
+
+    let start = StartGameCall {
+        game_id: caller.address.clone(),
+        session_id: 7,
+        player1,
+        player2: player2.clone(),
+        player1_points: 10,
+        player2_points: 20,
+    };
+    assert_eq!(
+        hub.calls(),
+        vec![
+            &env,
+            HubCall::StartGame(start),
+            HubCall::ExtendDeadline(7, 500, Some(player2)),
+            HubCall::EndGameWithOutcome(7, Outcome::Draw),
+            HubCall::EndGame(7, true),
+        ]
+    );
+}
+
+#[test]
+fn test_failing_hub_rejects_calls_and_records_nothing() {
+    let (env, hub, caller) = setup_test();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    hub.set_failing(&true);
+    assert!(caller.try_play(&hub.address, &7, &player1, &player2).is_err());
+    assert_eq!(hub.calls().len(), 0);
+
+    hub.set_failing(&false);
+    caller.play(&hub.address, &7, &player1, &player2);
+    assert_eq!(hub.calls().len(), 4);
+}
+
+#[test]
+fn test_queries_narrow_to_one_session() {
+    let (env, hub, caller) = setup_test();
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    caller.play(&hub.address, &7, &player1, &player2);
+    caller.play(&hub.address, &8, &player2, &player1);
+
+    assert_eq!(hub.call_count(), 8);
+    assert_eq!(hub.calls_for(&7).len(), 4);
+    assert!(hub.calls_for(&9).is_empty());
+
+    let start = hub.started(&8).unwrap();
+    assert_eq!(start.player1, player2);
+    assert_eq!(start.player2, player1);
+    assert_eq!(hub.started(&9), None);
+
+    // `play` reports a draw and then a player 1 win; the last result counts
+    assert_eq!(hub.player1_won(&7), Some(true));
+    assert_eq!(hub.player1_won(&9), None);
+}
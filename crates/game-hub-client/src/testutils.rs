@@ -0,0 +1,155 @@
+//! A Game Hub for game unit tests. `RecordingGameHub` keeps every call a game
+//! makes, in order, so tests can check the exact session ids, players, points
+//! and results sent to the hub. `calls_for`, `started` and `player1_won` narrow
+//! the record to one session. `set_failing(true)` makes every call fail the
+//! way a real hub rejecting it would, so tests can check the game rolls back.
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::Outcome;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RecordingHubError {
+    HubUnavailable = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StartGameCall {
+    pub game_id: Address,
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+}
+
+/// One call the hub accepted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HubCall {
+    StartGame(StartGameCall),
+    EndGame(u32, bool),                        // (session_id, player1_won)
+    EndGameWithOutcome(u32, Outcome),          // (session_id, outcome)
+    ExtendDeadline(u32, u32, Option<Address>), // (session_id, deadline_ledger, waiting_on)
+}
+
+impl HubCall {
+    pub fn session_id(&self) -> u32 {
+        match self {
+            HubCall::StartGame(call) => call.session_id,
+            HubCall::EndGame(session_id, _)
+            | HubCall::EndGameWithOutcome(session_id, _)
+            | HubCall::ExtendDeadline(session_id, _, _) => *session_id,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Calls,
+    Failing,
+}
+
+fn record(env: &Env, call: HubCall) {
+    if env.storage().instance().get(&DataKey::Failing).unwrap_or(false) {
+        panic_with_error!(env, RecordingHubError::HubUnavailable);
+    }
+    let mut calls: Vec<HubCall> = env.storage().instance().get(&DataKey::Calls).unwrap_or_else(|| Vec::new(env));
+    calls.push_back(call);
+    env.storage().instance().set(&DataKey::Calls, &calls);
+}
+
+#[contract]
+pub struct RecordingGameHub;
+
+#[contractimpl]
+impl RecordingGameHub {
+    pub fn start_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) {
+        // Same check as the real hub: only the game itself opens its sessions
+        game_id.require_auth();
+        record(
+            &env,
+            HubCall::StartGame(StartGameCall {
+                game_id,
+                session_id,
+                player1,
+                player2,
+                player1_points,
+                player2_points,
+            }),
+        );
+    }
+
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        record(&env, HubCall::EndGame(session_id, player1_won));
+    }
+
+    pub fn end_game_with_outcome(env: Env, session_id: u32, outcome: Outcome) {
+        record(&env, HubCall::EndGameWithOutcome(session_id, outcome));
+    }
+
+    pub fn extend_deadline(env: Env, session_id: u32, deadline_ledger: u32, waiting_on: Option<Address>) {
+        record(&env, HubCall::ExtendDeadline(session_id, deadline_ledger, waiting_on));
+    }
+
+    /// Make every hub call fail with `HubUnavailable`, or succeed again.
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage().instance().set(&DataKey::Failing, &failing);
+    }
+
+    /// Every call the hub accepted, oldest first.
+    pub fn calls(env: Env) -> Vec<HubCall> {
+        env.storage().instance().get(&DataKey::Calls).unwrap_or_else(|| Vec::new(&env))
+    }
+
+    pub fn call_count(env: Env) -> u32 {
+        Self::calls(env).len()
+    }
+
+    /// The calls for one session, oldest first.
+    pub fn calls_for(env: Env, session_id: u32) -> Vec<HubCall> {
+        let mut calls = Vec::new(&env);
+        for call in Self::calls(env.clone()).iter() {
+            if call.session_id() == session_id {
+                calls.push_back(call);
+            }
+        }
+        calls
+    }
+
+    /// The `start_game` call that opened a session, if there was one.
+    pub fn started(env: Env, session_id: u32) -> Option<StartGameCall> {
+        Self::calls(env).iter().find_map(|call| match call {
+            HubCall::StartGame(start) if start.session_id == session_id => Some(start),
+            _ => None,
+        })
+    }
+
+    /// Whether player 1 won, by the last result reported for the session.
+    /// `None` if the session hasn't ended, or ended in a draw or cancellation.
+    pub fn player1_won(env: Env, session_id: u32) -> Option<bool> {
+        let mut player1_won = None;
+        for call in Self::calls_for(env.clone(), session_id).iter() {
+            match call {
+                HubCall::EndGame(_, won) => player1_won = Some(won),
+                HubCall::EndGameWithOutcome(_, Outcome::Player1Won) => player1_won = Some(true),
+                HubCall::EndGameWithOutcome(_, Outcome::Player2Won) => player1_won = Some(false),
+                HubCall::EndGameWithOutcome(_, _) => player1_won = None,
+                _ => {}
+            }
+        }
+        player1_won
+    }
+}
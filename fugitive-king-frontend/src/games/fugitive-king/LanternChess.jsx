@@ -296,7 +296,7 @@ const LanternChess = () => {
         // Without this, any piece that has moved before throws "Piece has no commitment"
         // and silently fails, making the game appear frozen.
         const newSalt = Math.floor(Math.random() * 0xffffffff);
-        const newCommitment = await computeCommitment(row, col, newSalt);
+        const newCommitment = await computeCommitment(row, col, movingPiece.type, movingPiece.color, newSalt);
         setPieces(prev => prev.map(p =>
           p.id === movingPiece.id ? { ...p, salt: newSalt, commitment: newCommitment } : p
         ));
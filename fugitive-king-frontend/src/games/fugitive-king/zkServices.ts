@@ -10,7 +10,8 @@
  *        - Verifies move is legal for piece_type             (without revealing type)
  *        - Returns end_pos as public output
  *   3. If Bonsai configured: returns real Groth16 seal
- *      If dev mode: returns mock seal (same format, not cryptographically valid)
+ *      If dev mode: returns a dev seal — "DEV!" selector + SHA256(journal),
+ *      which the contract rejects unless its admin enabled the dev verifier
  *   4. Frontend passes proof to Soroban contract verify_move()
  *   5. Contract calls Nethermind Groth16 verifier on-chain (real enforcement)
  */
@@ -23,12 +24,15 @@ const CONTRACT_ID   = "CCBL5BNUPBW7HMHCZAQFIC6VTW7HACS2FWCOL3MGWGTZC4QLRVPD6S6O"
 const RPC_URL       = "https://soroban-testnet.stellar.org";
 const PROVER_URL    = import.meta.env.VITE_PROVER_URL || 'http://localhost:3001';
 
+// b"DEV!" — same selector the prover uses for dev-mode seals
+const DEV_SEAL_SELECTOR = '44455621';
+
 // Nethermind verifier already on testnet — used by the Soroban contract internally
 // export const NETHERMIND_VERIFIER = "CBY3GOBGQXDGRR4K2KYJO2UOXDW5NRW6UKIQHUBNBNU2V3BXQBXGTVX7";
 export const NETHERMIND_VERIFIER = "CDAEGIJHTD7Y3CQW6UY2EWVG5SOPATAYAHT6KQ7VL3WULPYJ6MHQH4TY";
 
 export interface ProofResult {
-  seal:           string;  // selector + groth16 proof (or mock)
+  seal:           string;  // selector + groth16 proof (or dev seal)
   journal:        string;  // hex of journal bytes (end_pos)
   journalSha256:  string;  // SHA256 of journal — for on-chain verify
   imageId:        string;  // METHOD_ID of circuit
@@ -57,15 +61,21 @@ const PIECE_TYPE_MAP: Record<string, number> = {
 
 // ── Board commitment ──────────────────────────────────────────────────────────
 
-export const computeCommitment = async (row: number, col: number, salt: number): Promise<string> => {
-  const data = new Uint8Array(6);
+// SHA256(row ‖ col ‖ piece type ‖ color ‖ salt) — piece and color are bound
+// so the circuit can't be told the piece is something else
+export const computeCommitment = async (
+  row: number, col: number, type: string, color: string, salt: number,
+): Promise<string> => {
+  const data = new Uint8Array(8);
   data[0] = row;
   data[1] = col;
+  data[2] = PIECE_TYPE_MAP[type] ?? 0;
+  data[3] = color === 'black' ? 1 : 0;
   // salt as 4 big-endian bytes
-  data[2] = (salt >> 24) & 0xff;
-  data[3] = (salt >> 16) & 0xff;
-  data[4] = (salt >> 8)  & 0xff;
-  data[5] =  salt        & 0xff;
+  data[4] = (salt >> 24) & 0xff;
+  data[5] = (salt >> 16) & 0xff;
+  data[6] = (salt >> 8)  & 0xff;
+  data[7] =  salt        & 0xff;
   const hashBuffer = await crypto.subtle.digest('SHA-256', data);
   return Array.from(new Uint8Array(hashBuffer))
     .map(b => b.toString(16).padStart(2, '0'))
@@ -75,7 +85,7 @@ export const computeCommitment = async (row: number, col: number, salt: number):
 export const initializePieceCommitments = async (pieces: Piece[]): Promise<Piece[]> => {
   return Promise.all(pieces.map(async (piece) => {
     const salt       = Math.floor(Math.random() * 0xffffffff);
-    const commitment = await computeCommitment(piece.row, piece.col, salt);
+    const commitment = await computeCommitment(piece.row, piece.col, piece.type, piece.color, salt);
     return { ...piece, salt, commitment };
   }));
 };
@@ -151,6 +161,7 @@ export class ZKServiceManager {
           start_pos:  [piece.row, piece.col],
           end_pos:    [toRow, toCol],
           piece_type: pieceTypeNum,
+          color:      piece.color,
           salt:       piece.salt,
           commitment: piece.commitment,
         }),
@@ -177,7 +188,7 @@ export class ZKServiceManager {
     const hashBuffer    = await crypto.subtle.digest('SHA-256', mockJournal);
     const sha256Hex     = Array.from(new Uint8Array(hashBuffer))
       .map(b => b.toString(16).padStart(2, '0')).join('');
-    const mockSeal      = '73c457ba' + '00'.repeat(256);
+    const mockSeal      = DEV_SEAL_SELECTOR + sha256Hex;
 
     return {
       seal:          mockSeal,
@@ -300,8 +300,19 @@ for (const contract of contracts) {
     console.log(`  WASM hash: ${wasmHash}`);
 
     console.log("  Deploying and initializing...");
-    const deployResult =
-      await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --game-hub ${mockGameHubId}`.text();
+    // The real hub only takes an admin; games are pointed at the mock hub
+    // until they are switched over with set_hub. The tournament reads session
+    // results, which only the real hub keeps, so it needs the real one.
+    const hubId = contract.isTournament
+      ? deployed["game-hub"] || existingContractIds["game-hub"]
+      : mockGameHubId;
+    if (!hubId) {
+      console.error("❌ Error: tournament needs a deployed game-hub; deploy game-hub first");
+      process.exit(1);
+    }
+    const deployResult = contract.isGameHub
+      ? await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress}`.text()
+      : await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --game-hub ${hubId}`.text();
     const contractId = deployResult.trim();
     deployed[contract.packageName] = contractId;
     console.log(`✅ ${contract.packageName} deployed: ${contractId}\n`);
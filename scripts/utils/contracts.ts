@@ -9,6 +9,8 @@ export type ContractInfo = {
   envKey: string;
   bindingsOutDir: string;
   isMockHub: boolean;
+  isGameHub: boolean;
+  isTournament: boolean;
 };
 
 export type ContractSelection = {
@@ -65,6 +67,8 @@ export async function getWorkspaceContracts(): Promise<ContractInfo[]> {
       envKey,
       bindingsOutDir: `bindings/${wasmName}`,
       isMockHub: packageName === "mock-game-hub" || wasmName === "mock_game_hub",
+      isGameHub: packageName === "game-hub",
+      isTournament: packageName === "tournament",
     });
   }
 